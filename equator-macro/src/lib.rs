@@ -1,6 +1,79 @@
 use proc_macro::*;
 
-fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
+const GRAMMAR: &str = "\n\
+	a condition is one of:\n\
+	- a comparison `lhs op rhs`, where `op` is `==`, `!=`, `<`, `<=`, `>`, `>=`, `~`, `~(tol)` or a custom comparator `:cmp:`\n\
+	- a comparison chain `lo < x < hi`, where each `<` may also be `<=`, or `hi > x > lo`, where each `>` may also be `>=`\n\
+	- `cond && cond` or `cond || cond`\n\
+	- `all(cond, ...)`, `any(cond, ...)`, `not(cond)` or `implies(premise, conclusion)`, where each `cond` may be labelled as `\"label\" => cond`\n\
+	- `(cond, \"fmt\", args...)`, whose message is printed when `cond` fails\n\
+	- `at_least(n, cond, ...)`, `at_most(n, cond, ...)` or `exactly(n, cond, ...)`\n\
+	- `item in collection`, where `collection` is a range, an array, a slice or a set\n\
+	- `value matches pattern`\n\
+	- `ok(value)`, `err(value)`, `some(value)` or `none(value)`\n\
+	- `for_all(x in iter => cond)` or `exists(x in iter => cond)`, where `x` is an identifier\n\
+	- any other boolean expression\n\
+	the condition may be preceded by `level = name;`, where `name` is `debug`, `expensive` or `paranoid`, to only test it when the level is enabled\n\
+	the whole condition of `assert!` may also be `let pattern = value`, whose bindings remain in scope, and `assert!(ok(value))`\n\
+	evaluates to the payload of `value`, as do `err`, `some` and `none`";
+
+/// A parse error, reported to the user as a `compile_error!` pointing at `span`.
+struct Error {
+	span: Span,
+	message: String,
+}
+
+impl Error {
+	fn new(span: Span, message: &str) -> Self {
+		Self {
+			span,
+			message: format!("{message}\n{GRAMMAR}"),
+		}
+	}
+
+	fn into_compile_error(self) -> TokenStream {
+		let span = self.span;
+		let mut message = Literal::string(&self.message);
+		message.set_span(span);
+
+		TokenStream::from_iter([
+			TokenTree::Punct({
+				let mut p = Punct::new(':', Spacing::Joint);
+				p.set_span(span);
+				p
+			}),
+			TokenTree::Punct({
+				let mut p = Punct::new(':', Spacing::Alone);
+				p.set_span(span);
+				p
+			}),
+			TokenTree::Ident(Ident::new("core", span)),
+			TokenTree::Punct({
+				let mut p = Punct::new(':', Spacing::Joint);
+				p.set_span(span);
+				p
+			}),
+			TokenTree::Punct({
+				let mut p = Punct::new(':', Spacing::Alone);
+				p.set_span(span);
+				p
+			}),
+			TokenTree::Ident(Ident::new("compile_error", span)),
+			TokenTree::Punct({
+				let mut p = Punct::new('!', Spacing::Alone);
+				p.set_span(span);
+				p
+			}),
+			TokenTree::Group({
+				let mut g = Group::new(Delimiter::Parenthesis, TokenStream::from_iter([TokenTree::Literal(message)]));
+				g.set_span(span);
+				g
+			}),
+		])
+	}
+}
+
+fn parse_expr(tokens: &[TokenTree]) -> Result<[usize; 4], Error> {
 	let start = tokens.as_ptr().addr();
 
 	let mut tokens = tokens;
@@ -8,14 +81,6 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 
 	macro_rules! try_give_up {
 		($tt:tt) => {
-			if let [TokenTree::Punct(p0), TokenTree::Punct(p1), rest @ ..] = tokens {
-				if p0.as_char() == '&' && p0.spacing() == Spacing::Joint && p1.as_char() == '&' {
-					give_up = true;
-					tokens = rest;
-					$tt;
-				}
-			}
-
 			if let [TokenTree::Punct(p0), rest @ ..] = tokens {
 				if p0.as_char() == '|' {
 					give_up = true;
@@ -37,12 +102,25 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 				{
 					tokens = rest;
 
-					while let [tt, rest @ ..] = tokens {
-						tokens = rest;
-						if let TokenTree::Punct(p0) = tt {
-							if p0.as_char() == '>' {
-								break;
+					// generic arguments may themselves be generic, e.g. `::<Vec<u8>>`
+					let mut depth = 1usize;
+					while depth > 0 {
+						match tokens {
+							[TokenTree::Punct(p0), TokenTree::Punct(p1), rest @ ..]
+								if p0.as_char() == '-' && p0.spacing() == Spacing::Joint && p1.as_char() == '>' =>
+							{
+								tokens = rest;
+							}
+							[TokenTree::Punct(p0), rest @ ..] if p0.as_char() == '<' => {
+								depth += 1;
+								tokens = rest;
 							}
+							[TokenTree::Punct(p0), rest @ ..] if p0.as_char() == '>' => {
+								depth -= 1;
+								tokens = rest;
+							}
+							[_, rest @ ..] => tokens = rest,
+							[] => return Err(Error::new(p2.span(), "unterminated turbofish, expected `>` to close this `::<`")),
 						}
 					}
 
@@ -123,13 +201,17 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 				tokens = rest;
 				'op: loop {
 					skip_generics!();
-					if let [tt, rest @ ..] = tokens {
-						tokens = rest;
-						if let TokenTree::Punct(p0) = tt {
-							if p0.as_char() == ':' {
-								op = offset!();
-								break 'op;
-							}
+					let [tt, rest @ ..] = tokens else {
+						return Err(Error::new(
+							p0.span(),
+							"expected a closing `:` after the comparator, custom comparisons are written as `lhs :cmp: rhs`",
+						));
+					};
+					tokens = rest;
+					if let TokenTree::Punct(p1) = tt {
+						if p1.as_char() == ':' {
+							op = offset!();
+							break 'op;
 						}
 					}
 				}
@@ -141,7 +223,7 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 		if let [TokenTree::Punct(p0), ..] = tokens {
 			if p0.as_char() == ',' {
 				lhs = offset!();
-				return [lhs, lhs, lhs, lhs + 1];
+				return Ok([lhs, lhs, lhs, lhs + 1]);
 			}
 		}
 		if let [_, rest @ ..] = tokens {
@@ -149,7 +231,7 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 			continue 'main;
 		}
 		lhs = offset!();
-		return [lhs, lhs, lhs, lhs];
+		return Ok([lhs, lhs, lhs, lhs]);
 	}
 
 	'main: loop {
@@ -160,9 +242,9 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 			if p0.as_char() == ',' {
 				rhs = offset!();
 				if give_up {
-					return [rhs, rhs, rhs, rhs + 1];
+					return Ok([rhs, rhs, rhs, rhs + 1]);
 				}
-				return [lhs, op, rhs, rhs + 1];
+				return Ok([lhs, op, rhs, rhs + 1]);
 			}
 		}
 		if let [_, rest @ ..] = tokens {
@@ -171,100 +253,1236 @@ fn parse_expr(tokens: &[TokenTree]) -> [usize; 4] {
 		}
 		rhs = offset!();
 		if give_up {
-			return [rhs, rhs, rhs, rhs];
+			return Ok([rhs, rhs, rhs, rhs]);
+		}
+		return Ok([lhs, op, rhs, rhs]);
+	}
+}
+
+/// Returns the direction of a comparison operator found by [`parse_expr`], which is `<` for `<` and
+/// `<=`, `>` for `>` and `>=`, and `None` for the other operators.
+fn ordering(op: &[TokenTree]) -> Option<char> {
+	match op {
+		[TokenTree::Punct(p)] | [TokenTree::Punct(p), TokenTree::Punct(_)] if matches!(p.as_char(), '<' | '>') => Some(p.as_char()),
+		_ => None,
+	}
+}
+
+/// Splits `tokens` on the top-level binary operator `cc`, where `c` is either `&` or `|`.
+fn split_binary(tokens: &[TokenTree], c: char) -> Vec<&[TokenTree]> {
+	let mut parts = vec![];
+	let mut start = 0;
+	let mut i = 0;
+	while i + 1 < tokens.len() {
+		if let [TokenTree::Punct(p0), TokenTree::Punct(p1)] = &tokens[i..i + 2] {
+			// the operator is binary only if it follows an operand, otherwise it is a double
+			// reference or an empty closure
+			let binary = i > start
+				&& match &tokens[i - 1] {
+					TokenTree::Punct(p) => p.as_char() == '?',
+					_ => true,
+				};
+			if binary && p0.as_char() == c && p0.spacing() == Spacing::Joint && p1.as_char() == c {
+				parts.push(&tokens[start..i]);
+				start = i + 2;
+				i = start;
+				continue;
+			}
 		}
-		return [lhs, op, rhs, rhs];
+		i += 1;
+	}
+	parts.push(&tokens[start..]);
+	parts
+}
+
+/// Parses the condition at the start of `tokens`, up to the next top-level comma.
+///
+/// `span` is used to report an empty condition when `tokens` is empty.
+fn parse(tokens: &[TokenTree], span: Span) -> Result<(TokenStream, usize), Error> {
+	let [_, _, end, next] = parse_expr(tokens)?;
+	if end == 0 {
+		let span = tokens.first().map_or(span, |tt| tt.span());
+		return Err(Error::new(span, "expected a condition"));
+	}
+	Ok((parse_cond(&tokens[..end])?, next))
+}
+
+/// Same as [`parse`], but the condition may be preceded by a label, as in `"label" => cond`.
+fn parse_labelled(tokens: &[TokenTree], span: Span) -> Result<(TokenStream, usize), Error> {
+	let [TokenTree::Literal(label), TokenTree::Punct(p0), TokenTree::Punct(p1), rest @ ..] = tokens else {
+		return parse(tokens, span);
+	};
+	if p0.as_char() != '=' || p0.spacing() != Spacing::Joint || p1.as_char() != '>' {
+		return parse(tokens, span);
+	}
+	let label_str = label.to_string();
+	if !label_str.starts_with(['"', 'r']) {
+		return Err(Error::new(
+			label.span(),
+			"expected a string literal as the label, as in `\"label\" => cond`",
+		));
 	}
+	let (cond, next) = parse(rest, p1.span())?;
+	let cond = TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("label", Span::call_site())),
+		TokenTree::Literal(label.clone()),
+		paren(cond),
+	]);
+	Ok((cond, next + 3))
 }
 
-fn parse(tokens: &[TokenTree]) -> (TokenStream, usize) {
-	let [lhs, op, rhs, next] = parse_expr(tokens);
+/// Emits `cond`, parsed from `tokens`, as `@lazy (source) (cond)`, so that it is only evaluated
+/// if needed. `source` is the condition without its label and message, which is reported as
+/// written if it is not evaluated.
+fn lazy(tokens: &[TokenTree], cond: TokenStream) -> TokenStream {
+	TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("lazy", Span::call_site())),
+		paren(unlabelled(tokens, &cond)),
+		paren(cond),
+	])
+}
+
+/// Returns the source of `cond`, parsed from `tokens`, without its label and message.
+fn unlabelled(tokens: &[TokenTree], cond: &TokenStream) -> TokenStream {
+	let parts = Vec::from_iter(cond.clone());
+	if let [TokenTree::Punct(at), TokenTree::Ident(kind), _, TokenTree::Group(inner)] = &*parts {
+		match &*kind.to_string() {
+			// `"label" => cond`
+			"label" if at.as_char() == '@' => return unlabelled(&tokens[3..], &inner.stream()),
+			// `(cond, "fmt", args...)`, possibly in more parentheses
+			"message" if at.as_char() == '@' => {
+				if let [TokenTree::Group(g)] = tokens {
+					let g = Vec::from_iter(g.stream());
+					if let Ok([_, _, end, _]) = parse_expr(&g) {
+						return if end == g.len() {
+							unlabelled(&g, cond)
+						} else {
+							unlabelled(&g[..end], &inner.stream())
+						};
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+	TokenStream::from_iter(tokens.iter().cloned())
+}
+
+fn paren(stream: TokenStream) -> TokenTree {
+	TokenTree::Group(Group::new(Delimiter::Parenthesis, stream))
+}
+
+/// Parses a single condition, with no trailing comma.
+fn parse_cond(tokens: &[TokenTree]) -> Result<TokenStream, Error> {
+	if let [TokenTree::Group(g)] = tokens {
+		if g.delimiter() == Delimiter::Parenthesis {
+			let inner = &*Vec::from_iter(g.stream());
+			let [_, _, end, next] = parse_expr(inner)?;
+			// a top-level comma would make it a tuple, unless it is followed by a message
+			if end == inner.len() {
+				if inner.is_empty() {
+					return Err(Error::new(g.span(), "expected a condition"));
+				}
+				return parse_cond(inner);
+			}
+			if let Some(TokenTree::Literal(fmt)) = inner.get(next) {
+				if fmt.to_string().starts_with(['"', 'r']) {
+					if end == 0 {
+						return Err(Error::new(
+							g.span(),
+							"expected a condition before the message, as in `(cond, \"fmt\", args...)`",
+						));
+					}
+					return Ok(TokenStream::from_iter([
+						TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+						TokenTree::Ident(Ident::new("message", Span::call_site())),
+						paren(TokenStream::from_iter(inner[next..].iter().cloned())),
+						paren(parse_cond(&inner[..end])?),
+					]));
+				}
+			}
+		}
+	}
+
+	if let Some(TokenTree::Ident(l)) = tokens.first() {
+		if l.to_string() == "let" {
+			return Err(Error::new(l.span(), "`let pattern = value` must be the whole condition"));
+		}
+	}
+
+	// `||` has a lower precedence than `&&`, which has a lower precedence than comparisons.
+	// `lhs && rhs...` is emitted as `@and (lhs) (rhs source) (rhs)...`, since the right operands
+	// must only be evaluated while the result is not known yet, and are reported as written otherwise
+	for (c, f) in [('|', "or"), ('&', "and")] {
+		let parts = split_binary(tokens, c);
+		if parts.len() > 1 {
+			let mut cond = vec![
+				TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+				TokenTree::Ident(Ident::new(f, Span::call_site())),
+			];
+			for (i, part) in parts.into_iter().enumerate() {
+				if part.is_empty() {
+					// only the last operand can be empty, since `&&` and `||` are otherwise unary
+					let span = tokens[tokens.len() - 1].span();
+					return Err(Error::new(span, &format!("expected a condition after `{c}{c}`")));
+				}
+				if i > 0 {
+					cond.push(paren(TokenStream::from_iter(part.iter().cloned())));
+				}
+				cond.push(paren(parse_cond(part)?));
+			}
+			return Ok(TokenStream::from_iter(cond));
+		}
+	}
+
+	// `matches` can only be an operator when it follows an operand, since two consecutive operands
+	// are not a valid expression. patterns may contain comparison operators in guards and ranges, so
+	// this is checked first
+	let matches = (1..tokens.len()).find(|&i| {
+		matches!(&tokens[i], TokenTree::Ident(m) if m.to_string() == "matches")
+			&& !matches!(&tokens[i - 1], TokenTree::Punct(p) if p.as_char() != '?')
+	});
+	if let Some(i) = matches {
+		if i + 1 == tokens.len() {
+			return Err(Error::new(tokens[i].span(), "expected a pattern after `matches`"));
+		}
+		return Ok(leaf([&tokens[..i]], |[value]| {
+			TokenStream::from_iter([
+				TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+				TokenTree::Ident(Ident::new("match", Span::call_site())),
+				paren(TokenStream::from_iter(tokens.iter().cloned())),
+				paren(value),
+				paren(TokenStream::from_iter(tokens[i + 1..].iter().cloned())),
+			])
+		}));
+	}
+
+	// `in` has a lower precedence than any operator, including ranges
+	let contains = tokens.iter().position(|tt| matches!(tt, TokenTree::Ident(i) if i.to_string() == "in"));
+	if let Some(i) = contains {
+		if i == 0 {
+			return Err(Error::new(tokens[i].span(), "expected an expression before `in`"));
+		}
+		if i + 1 == tokens.len() {
+			return Err(Error::new(tokens[i].span(), "expected a range or a collection after `in`"));
+		}
+		return Ok(leaf([&tokens[..i], &tokens[i + 1..]], |[item, collection]| {
+			TokenStream::from_iter([
+				TokenTree::Ident(Ident::new("in", Span::call_site())),
+				paren(TokenStream::from_iter(
+					item.into_iter()
+						.chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))])
+						.chain(collection),
+				)),
+			])
+		}));
+	}
+
+	let [lhs, op, rhs, _] = parse_expr(tokens)?;
 	if lhs < op {
-		let args = [TokenTree::Group(Group::new(
-			Delimiter::Parenthesis,
-			TokenStream::from_iter(
-				tokens[..lhs]
-					.iter()
-					.cloned()
-					.chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))])
-					.chain(tokens[op..rhs].iter().cloned()),
-			),
-		))];
+		let op_str = String::from_iter(tokens[lhs..op].iter().map(|tt| tt.to_string()));
+		if lhs == 0 {
+			return Err(Error::new(tokens[0].span(), &format!("expected an expression before `{op_str}`")));
+		}
+		if op == rhs {
+			return Err(Error::new(tokens[op - 1].span(), &format!("expected an expression after `{op_str}`")));
+		}
 
-		if let TokenTree::Punct(p) = &tokens[lhs] {
-			if p.as_char() == ':' {
-				return (
-					TokenStream::from_iter(
+		// the tokens preceding the operands, and the start of the rhs
+		let (prefix, rhs_start) = match &tokens[lhs] {
+			TokenTree::Punct(p) if p.as_char() == ':' => {
+				if op - lhs == 2 {
+					return Err(Error::new(p.span(), "expected a comparator between `:` and `:`"));
+				}
+				(
+					Vec::from_iter(
 						tokens[lhs + 1..op - 1]
 							.iter()
 							.cloned()
-							.chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))])
-							.chain(args),
+							.chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))]),
 					),
-					next,
-				);
+					op,
+				)
 			}
-		}
-
-		if let TokenTree::Punct(p) = &tokens[lhs] {
-			if p.as_char() == '~' {
-				return (
-					TokenStream::from_iter(
-						[
+			TokenTree::Punct(p) if p.as_char() == '~' => {
+				// `lhs ~(tol) rhs`, as opposed to `lhs ~ (rhs)...` where the group is part of the rhs
+				let inline = match &tokens[op..rhs] {
+					[TokenTree::Group(tol), next, ..]
+						if tol.delimiter() == Delimiter::Parenthesis
+							&& match next {
+								TokenTree::Ident(i) => i.to_string() != "as",
+								TokenTree::Literal(_) => true,
+								TokenTree::Group(g) => g.delimiter() == Delimiter::Parenthesis,
+								TokenTree::Punct(_) => false,
+							} =>
+					{
+						Some(tol)
+					}
+					_ => None,
+				};
+				if let Some(tol) = inline {
+					if tol.stream().is_empty() {
+						return Err(Error::new(tol.span(), "expected a tolerance, as in `lhs ~(tol) rhs`"));
+					}
+					(
+						vec![
+							TokenTree::Punct(Punct::new('~', Spacing::Alone)),
+							TokenTree::Group(tol.clone()),
+							TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+						],
+						op + 1,
+					)
+				} else {
+					// resolved where the condition is written, so that a local `approx_eq` is used
+					(
+						vec![
 							TokenTree::Ident(Ident::new("approx_eq", p.span())),
 							TokenTree::Punct(Punct::new(',', Spacing::Alone)),
 							TokenTree::Punct(Punct::new('~', Spacing::Alone)),
-						]
-						.into_iter()
-						.chain(args),
-					),
-					next,
-				);
+						],
+						op,
+					)
+				}
+			}
+			_ => (tokens[lhs..op].to_vec(), op),
+		};
+
+		// `lo <= x < hi`, where the middle operand is shared by both comparisons
+		if let Some(order) = ordering(&tokens[lhs..op]) {
+			let [mid, op2, _, _] = parse_expr(&tokens[op..rhs])?;
+			let (mid, op2) = (op + mid, op + op2);
+			if mid < op2 {
+				let op2_str = String::from_iter(tokens[mid..op2].iter().map(|tt| tt.to_string()));
+				if ordering(&tokens[mid..op2]) != Some(order) {
+					return Err(Error::new(
+						tokens[mid].span(),
+						&format!("cannot chain `{op_str}` with `{op2_str}`, comparison chains must either be all `<`/`<=` or all `>`/`>=`"),
+					));
+				}
+				if mid == op {
+					return Err(Error::new(tokens[mid].span(), &format!("expected an expression before `{op2_str}`")));
+				}
+				if op2 == rhs {
+					return Err(Error::new(tokens[op2 - 1].span(), &format!("expected an expression after `{op2_str}`")));
+				}
+				let ops = [&tokens[lhs..op], &tokens[mid..op2]];
+				return Ok(leaf([&tokens[..lhs], &tokens[op..mid], &tokens[op2..rhs]], |[lhs, mid, rhs]| {
+					let comma = || TokenTree::Punct(Punct::new(',', Spacing::Alone));
+					TokenStream::from_iter([
+						TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+						TokenTree::Ident(Ident::new("chain", Span::call_site())),
+						paren(TokenStream::from_iter(ops[0].iter().cloned())),
+						paren(TokenStream::from_iter(ops[1].iter().cloned())),
+						paren(TokenStream::from_iter(
+							lhs.into_iter().chain([comma()]).chain(mid).chain([comma()]).chain(rhs),
+						)),
+					])
+				}));
 			}
 		}
 
-		return (TokenStream::from_iter(tokens[lhs..op].iter().cloned().chain(args)), next);
+		return Ok(leaf([&tokens[..lhs], &tokens[rhs_start..rhs]], |[lhs, rhs]| {
+			TokenStream::from_iter(prefix.iter().cloned().chain([paren(TokenStream::from_iter(
+				lhs.into_iter().chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))]).chain(rhs),
+			))]))
+		}));
 	}
-	assert_eq!(lhs, op);
-	assert_eq!(op, rhs);
 
 	let tokens = &tokens[..lhs];
 
 	if let [TokenTree::Ident(f), TokenTree::Group(g)] = tokens {
-		if matches!(&*f.to_string(), "any" | "all") {
+		let f_str = f.to_string();
+		if matches!(&*f_str, "any" | "all" | "not" | "implies" | "at_least" | "at_most" | "exactly") {
+			let span = g.span_close();
 			let mut start = 0;
 			let mut cond = vec![];
 			let g = &*Vec::from_iter(g.stream());
+			if matches!(&*f_str, "at_least" | "at_most" | "exactly") {
+				// the first argument is the expected count, not a condition
+				let [_, _, end, next] = parse_expr(g)?;
+				if end == 0 {
+					let span = g.first().map_or(span, |tt| tt.span());
+					return Err(Error::new(
+						span,
+						&format!("expected the number of conditions that must hold, as in `{f_str}(n, cond, ...)`"),
+					));
+				}
+				cond.push(paren(TokenStream::from_iter(g[..end].iter().cloned())));
+				start = next;
+			}
 			while start < g.len() {
-				let (c, next) = parse(&g[start..]);
-				cond.push(TokenTree::Group(Group::new(Delimiter::Parenthesis, c)));
+				let (mut c, next) = parse_labelled(&g[start..], span)?;
+				if f_str == "implies" && cond.len() == 1 {
+					// the conclusion must only be evaluated if the premise holds
+					let mut source = &g[start..start + next];
+					if let [rest @ .., TokenTree::Punct(p)] = source {
+						if p.as_char() == ',' {
+							source = rest;
+						}
+					}
+					c = lazy(source, c);
+				}
+				cond.push(paren(c));
 				start += next;
 			}
-			return (
-				TokenStream::from_iter([
-					TokenTree::Ident(f.clone()),
-					TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from_iter(cond))),
-				]),
-				next,
-			);
+
+			let expected = match &*f_str {
+				"not" => Some((1, "`not(cond)` expects exactly one condition")),
+				"implies" => Some((2, "`implies(premise, conclusion)` expects exactly two conditions")),
+				_ => None,
+			};
+			if let Some((n, message)) = expected {
+				if cond.len() != n {
+					return Err(Error::new(f.span(), message));
+				}
+			}
+
+			return Ok(TokenStream::from_iter([TokenTree::Ident(f.clone()), paren(TokenStream::from_iter(cond))]));
+		}
+		if matches!(&*f_str, "for_all" | "exists") && g.delimiter() == Delimiter::Parenthesis {
+			return parse_quantifier(tokens, f, g);
+		}
+		if UNWRAP.contains(&&*f_str) && g.delimiter() == Delimiter::Parenthesis {
+			let value = &*Vec::from_iter(g.stream());
+			if value.is_empty() {
+				return Err(Error::new(g.span(), &format!("expected a value, as in `{f_str}(value)`")));
+			}
+			return Ok(leaf([value], |[value]| unwrap(tokens, f, value)));
+		}
+	}
+
+	if let Some(call) = parse_call(tokens) {
+		return Ok(call);
+	}
+
+	Ok(leaf([tokens], |[cond]| cond))
+}
+
+/// Parses `quantifier(x in iter => cond)` as `@quant (source) quantifier (x) (iter) (cond)`.
+fn parse_quantifier(tokens: &[TokenTree], quantifier: &Ident, g: &Group) -> Result<TokenStream, Error> {
+	let usage = format!("expected `{quantifier}(x in iter => cond)`, where `x` is an identifier");
+	let inner = &*Vec::from_iter(g.stream());
+	let [TokenTree::Ident(var), TokenTree::Ident(in_), rest @ ..] = inner else {
+		return Err(Error::new(g.span(), &usage));
+	};
+	if in_.to_string() != "in" || matches!(&*var.to_string(), "mut" | "ref" | "_") {
+		return Err(Error::new(var.span(), &usage));
+	}
+	// `=>` can only appear at the top level of the iterator as the separator, since match arms are
+	// within braces
+	let arrow = (1..rest.len()).find(|&i| {
+		matches!((&rest[i - 1], &rest[i]), (TokenTree::Punct(p0), TokenTree::Punct(p1))
+			if p0.as_char() == '=' && p0.spacing() == Spacing::Joint && p1.as_char() == '>')
+	});
+	let Some(arrow) = arrow else {
+		return Err(Error::new(g.span_close(), &usage));
+	};
+	let (iter, cond) = (&rest[..arrow - 1], &rest[arrow + 1..]);
+	if iter.is_empty() {
+		return Err(Error::new(in_.span(), "expected an iterator after `in`"));
+	}
+	if cond.is_empty() {
+		return Err(Error::new(rest[arrow].span(), "expected a condition after `=>`"));
+	}
+
+	Ok(TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("quant", Span::call_site())),
+		paren(TokenStream::from_iter(tokens.iter().cloned())),
+		TokenTree::Ident(quantifier.clone()),
+		paren(TokenStream::from_iter([TokenTree::Ident(var.clone())])),
+		paren(TokenStream::from_iter(iter.iter().cloned())),
+		paren(parse_cond(cond)?),
+	]))
+}
+
+/// Leaves testing the variant of an `Option` or a `Result`, which evaluate to its payload when they
+/// are the whole condition of `assert!`.
+const UNWRAP: &[&str] = &["ok", "err", "some", "none"];
+
+/// Builds `@unwrap (source) kind (value)`, where `source` is the whole leaf.
+fn unwrap(source: &[TokenTree], kind: &Ident, value: TokenStream) -> TokenStream {
+	TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("unwrap", Span::call_site())),
+		paren(TokenStream::from_iter(source.iter().cloned())),
+		TokenTree::Ident(kind.clone()),
+		paren(value),
+	])
+}
+
+/// Whether `tokens` is a place made of a variable, its fields and indices, which can be borrowed
+/// again after the call without evaluating anything twice.
+fn is_place(tokens: &[TokenTree]) -> bool {
+	let [TokenTree::Ident(head), rest @ ..] = tokens else {
+		return false;
+	};
+	if matches!(&*head.to_string(), "true" | "false" | "mut") {
+		return false;
+	}
+	let mut rest = rest;
+	loop {
+		rest = match rest {
+			[] => return true,
+			[TokenTree::Punct(p), TokenTree::Ident(_) | TokenTree::Literal(_), rest @ ..] if p.as_char() == '.' => rest,
+			[TokenTree::Group(g), rest @ ..]
+				if g.delimiter() == Delimiter::Bracket && !g.stream().into_iter().any(|tt| matches!(tt, TokenTree::Group(_))) =>
+			{
+				rest
+			}
+			_ => return false,
+		};
+	}
+}
+
+/// Parses a method call, as `@call (call) (receiver) kind (.method(args)) ((capture) (source))...`.
+///
+/// The receiver is captured before the call, so that methods taking `&mut self` or `self` work as
+/// usual. If it is a place, `kind` is `place` and the call is evaluated as written. If it is a
+/// temporary, `kind` is `temp` and the method is called on it once it is bound by value. Other
+/// receivers are not decomposed.
+///
+/// Arguments that are literals or borrowed places, such as `&key`, are captured, other arguments
+/// are only forwarded to the method since they may be moved.
+fn parse_call(tokens: &[TokenTree]) -> Option<TokenStream> {
+	let [recv @ .., TokenTree::Punct(dot), TokenTree::Ident(_), TokenTree::Group(args)] = tokens else {
+		return None;
+	};
+	if recv.is_empty() || dot.as_char() != '.' || args.delimiter() != Delimiter::Parenthesis {
+		return None;
+	}
+	// prefix and binary operators bind more loosely than the method call, e.g. `!x.is_empty()`
+	if recv
+		.iter()
+		.any(|tt| matches!(tt, TokenTree::Punct(p) if !matches!(p.as_char(), '.' | ':' | '?')))
+	{
+		return None;
+	}
+	let kind = match recv {
+		_ if is_place(recv) => "place",
+		[TokenTree::Literal(_)] | [.., TokenTree::Punct(_)] => "temp",
+		[.., TokenTree::Ident(_), TokenTree::Group(g)] if g.delimiter() == Delimiter::Parenthesis => "temp",
+		_ => return None,
+	};
+
+	let mut captures = vec![];
+	let args = Vec::from_iter(args.stream());
+	// closures and generic arguments may contain commas that do not separate arguments
+	if !args
+		.iter()
+		.any(|tt| matches!(tt, TokenTree::Punct(p) if matches!(p.as_char(), '|' | '<')))
+	{
+		for arg in args.split(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ',')) {
+			match arg {
+				[TokenTree::Punct(p), place @ ..] if p.as_char() == '&' && is_place(place) => {
+					captures.push(paren(TokenStream::from_iter(arg.iter().cloned())));
+					captures.push(paren(TokenStream::from_iter(place.iter().cloned())));
+				}
+				[TokenTree::Literal(lit)] => {
+					captures.push(paren(TokenStream::from_iter([
+						TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+						TokenTree::Literal(lit.clone()),
+					])));
+					captures.push(paren(TokenStream::from_iter([TokenTree::Literal(lit.clone())])));
+				}
+				_ => {}
+			}
 		}
 	}
 
-	(TokenStream::from_iter(tokens.iter().cloned()), next)
+	Some(TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("call", Span::call_site())),
+		paren(TokenStream::from_iter(tokens.iter().cloned())),
+		paren(TokenStream::from_iter(recv.iter().cloned())),
+		TokenTree::Ident(Ident::new(kind, Span::call_site())),
+		paren(TokenStream::from_iter(tokens[recv.len()..].iter().cloned())),
+		paren(TokenStream::from_iter(captures)),
+	]))
+}
+
+/// Whether operands are instrumented to record the values of their sub-expressions.
+const POWER: bool = cfg!(feature = "power");
+
+/// Keywords starting expressions that may contain statements or bindings, which are not instrumented.
+const OPAQUE: &[&str] = &[
+	"async", "break", "const", "continue", "for", "if", "let", "loop", "match", "move", "return", "static", "unsafe", "while",
+];
+
+/// Builds a leaf condition from its operands.
+///
+/// In power-assert mode, the leaf is emitted as `@power (leaf) (places,...) (instrumented leaf)` if
+/// any of its operands has intermediate values worth recording.
+fn leaf<const N: usize>(operands: [&[TokenTree]; N], build: impl Fn([TokenStream; N]) -> TokenStream) -> TokenStream {
+	let source = build(operands.map(|tokens| TokenStream::from_iter(tokens.iter().cloned())));
+	if !POWER {
+		return source;
+	}
+
+	let mut power = Instrument::default();
+	let imp = build(operands.map(|tokens| power.operand(tokens)));
+	if !power.changed && power.places.is_empty() {
+		return source;
+	}
+	TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("power", Span::call_site())),
+		paren(source),
+		paren(TokenStream::from_iter(power.places)),
+		paren(imp),
+	])
+}
+
+/// Rewrites operands so that the value of each of their sub-expressions is recorded into the log
+/// declared by [`expand`].
+///
+/// Places such as `x` or `a.b` are recorded by reference before the leaf is evaluated, while the
+/// results of calls are recorded as they are produced, then passed on to the rest of the operand.
+/// The operand itself is not recorded, since its value is already reported.
+#[derive(Default)]
+struct Instrument {
+	/// Comma-terminated expressions recording the places read by the operands.
+	places: Vec<TokenTree>,
+	/// Sources of the recorded places, so that each one is only recorded once.
+	recorded: Vec<String>,
+	/// Whether the result of a call is recorded.
+	changed: bool,
+}
+
+impl Instrument {
+	/// Instruments `tokens` up to the first token that is not understood, from which the operand is
+	/// forwarded as is.
+	fn operand(&mut self, tokens: &[TokenTree]) -> TokenStream {
+		let mut out = vec![];
+		let mut i = 0;
+		while i < tokens.len() {
+			match &tokens[i] {
+				// ranges
+				TokenTree::Punct(p) if p.as_char() == '.' && p.spacing() == Spacing::Joint => {
+					let end = usize::min(i + 2, tokens.len());
+					out.extend(tokens[i..end].iter().cloned());
+					i = end;
+				}
+				// closures, qualified paths, labels and method calls on an atom that was not understood
+				TokenTree::Punct(p) if matches!(p.as_char(), '|' | '<' | '#' | '\'' | '.') => break,
+				// prefix and binary operators
+				TokenTree::Punct(_) => {
+					out.push(tokens[i].clone());
+					i += 1;
+				}
+				TokenTree::Ident(m) if m.to_string() == "mut" && matches!(out.last(), Some(TokenTree::Punct(p)) if p.as_char() == '&') => {
+					out.push(tokens[i].clone());
+					i += 1;
+				}
+				_ => {
+					let Some(end) = self.atom(tokens, i, &mut out) else {
+						break;
+					};
+					i = end;
+					// an atom must be followed by an operator
+					if !matches!(tokens.get(i), None | Some(TokenTree::Punct(_))) {
+						break;
+					}
+				}
+			}
+		}
+		out.extend(tokens[i..].iter().cloned());
+		TokenStream::from_iter(out)
+	}
+
+	/// Instruments the atom starting at `start` along with its postfix expressions, and returns
+	/// where it ends.
+	fn atom(&mut self, tokens: &[TokenTree], start: usize, out: &mut Vec<TokenTree>) -> Option<usize> {
+		let mut expr = vec![];
+		let mut i = start;
+		let mut place = false;
+
+		match &tokens[i] {
+			TokenTree::Ident(ident) => {
+				if OPAQUE.contains(&&*ident.to_string()) {
+					return None;
+				}
+				i = path_end(tokens, i)?;
+				expr.extend(tokens[start..i].iter().cloned());
+				match &tokens[i..] {
+					[TokenTree::Punct(p), TokenTree::Group(_), ..] if p.as_char() == '!' => {
+						expr.extend(tokens[i..i + 2].iter().cloned());
+						i += 2;
+						self.wrap(&mut expr, tokens, start, i);
+					}
+					[TokenTree::Group(g), ..] if g.delimiter() == Delimiter::Parenthesis => {
+						expr.push(tokens[i].clone());
+						i += 1;
+						self.wrap(&mut expr, tokens, start, i);
+					}
+					_ => {
+						// constants, unit structs and enum variants are left out
+						let last = tokens[start..i].iter().rev().find_map(|tt| match tt {
+							TokenTree::Ident(ident) => Some(ident.to_string()),
+							_ => None,
+						});
+						place = last.is_some_and(|last| !matches!(&*last, "true" | "false") && !last.starts_with(char::is_uppercase));
+						if place {
+							self.place(tokens, start, i);
+						}
+					}
+				}
+			}
+			TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis => {
+				let inner = Vec::from_iter(g.stream());
+				// tuples are forwarded as is
+				let stream = if inner.iter().any(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ',')) {
+					g.stream()
+				} else {
+					self.operand(&inner)
+				};
+				let mut group = Group::new(Delimiter::Parenthesis, stream);
+				group.set_span(g.span());
+				expr.push(TokenTree::Group(group));
+				i += 1;
+			}
+			tt => {
+				expr.push(tt.clone());
+				i += 1;
+			}
+		}
+
+		loop {
+			match &tokens[i..] {
+				[TokenTree::Punct(p), TokenTree::Ident(m), ..] if p.as_char() == '.' && m.to_string() == "await" => {
+					expr.extend(tokens[i..i + 2].iter().cloned());
+					i += 2;
+					place = false;
+				}
+				[TokenTree::Punct(p), TokenTree::Ident(_), TokenTree::Group(g), ..]
+					if p.as_char() == '.' && p.spacing() == Spacing::Alone && g.delimiter() == Delimiter::Parenthesis =>
+				{
+					expr.extend(tokens[i..i + 3].iter().cloned());
+					i += 3;
+					place = false;
+					self.wrap(&mut expr, tokens, start, i);
+				}
+				[TokenTree::Punct(p), TokenTree::Ident(_), TokenTree::Punct(c), ..] if p.as_char() == '.' && c.as_char() == ':' => {
+					let Some(end) = generics_end(tokens, i + 2) else {
+						break;
+					};
+					let Some(TokenTree::Group(g)) = tokens.get(end) else {
+						break;
+					};
+					if g.delimiter() != Delimiter::Parenthesis {
+						break;
+					}
+					expr.extend(tokens[i..end + 1].iter().cloned());
+					i = end + 1;
+					place = false;
+					self.wrap(&mut expr, tokens, start, i);
+				}
+				[TokenTree::Punct(p), TokenTree::Ident(_) | TokenTree::Literal(_), ..] if p.as_char() == '.' && p.spacing() == Spacing::Alone => {
+					expr.extend(tokens[i..i + 2].iter().cloned());
+					i += 2;
+					if place {
+						self.place(tokens, start, i);
+					}
+				}
+				[TokenTree::Punct(p), ..] if p.as_char() == '?' => {
+					expr.push(tokens[i].clone());
+					i += 1;
+					place = false;
+				}
+				[TokenTree::Group(g), ..] if g.delimiter() == Delimiter::Bracket => {
+					expr.push(tokens[i].clone());
+					i += 1;
+					place = false;
+				}
+				[TokenTree::Group(g), ..] if g.delimiter() == Delimiter::Parenthesis => {
+					expr.push(tokens[i].clone());
+					i += 1;
+					place = false;
+					self.wrap(&mut expr, tokens, start, i);
+				}
+				_ => break,
+			}
+		}
+
+		out.extend(expr);
+		Some(i)
+	}
+
+	/// Records the place `tokens[start..end]` by reference, before the leaf is evaluated.
+	fn place(&mut self, tokens: &[TokenTree], start: usize, end: usize) {
+		if start == 0 && end == tokens.len() {
+			return;
+		}
+		let source = &tokens[start..end];
+		let key = TokenStream::from_iter(source.iter().cloned()).to_string();
+		if self.recorded.contains(&key) {
+			return;
+		}
+		self.recorded.push(key);
+		self.places.extend(record(
+			source,
+			[
+				TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+				paren(TokenStream::from_iter(source.iter().cloned())),
+			],
+		));
+		self.places.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+	}
+
+	/// Replaces `expr`, the instrumented form of the call `tokens[start..end]`, with
+	/// `(match expr { value => { record(value); value } })`.
+	fn wrap(&mut self, expr: &mut Vec<TokenTree>, tokens: &[TokenTree], start: usize, end: usize) {
+		if start == 0 && end == tokens.len() {
+			return;
+		}
+		self.changed = true;
+
+		let value = Ident::new("__equator_value", Span::mixed_site());
+		let body = TokenStream::from_iter(
+			record(
+				&tokens[start..end],
+				[TokenTree::Punct(Punct::new('&', Spacing::Alone)), TokenTree::Ident(value.clone())],
+			)
+			.into_iter()
+			.chain([TokenTree::Punct(Punct::new(';', Spacing::Alone)), TokenTree::Ident(value.clone())]),
+		);
+		*expr = vec![paren(TokenStream::from_iter(
+			[TokenTree::Ident(Ident::new("match", Span::call_site()))]
+				.into_iter()
+				.chain(expr.drain(..))
+				.chain([TokenTree::Group(Group::new(
+					Delimiter::Brace,
+					TokenStream::from_iter([
+						TokenTree::Ident(value),
+						TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+						TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+						TokenTree::Group(Group::new(Delimiter::Brace, body)),
+					]),
+				))]),
+		))];
+	}
+}
+
+/// Builds `log.record(stringify!(source), log.capture(value).get())`.
+fn record(source: &[TokenTree], value: impl IntoIterator<Item = TokenTree>) -> TokenStream {
+	let log = Ident::new("__equator_log", Span::mixed_site());
+	TokenStream::from_iter([
+		TokenTree::Ident(log.clone()),
+		TokenTree::Punct(Punct::new('.', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("record", Span::call_site())),
+		paren(TokenStream::from_iter([
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("core", Span::call_site())),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("stringify", Span::call_site())),
+			TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+			paren(TokenStream::from_iter(source.iter().cloned())),
+			TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+			TokenTree::Ident(log),
+			TokenTree::Punct(Punct::new('.', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("capture", Span::call_site())),
+			paren(TokenStream::from_iter(value)),
+			TokenTree::Punct(Punct::new('.', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("get", Span::call_site())),
+			paren(TokenStream::new()),
+		])),
+	])
+}
+
+/// Returns the end of the path starting at `start`, such as `a`, `a::b` or `Vec::<u8>::new`.
+fn path_end(tokens: &[TokenTree], start: usize) -> Option<usize> {
+	let mut i = start + 1;
+	loop {
+		match &tokens[i..] {
+			[TokenTree::Punct(p0), TokenTree::Punct(p1), TokenTree::Ident(_), ..]
+				if p0.as_char() == ':' && p0.spacing() == Spacing::Joint && p1.as_char() == ':' =>
+			{
+				i += 3
+			}
+			[TokenTree::Punct(p0), TokenTree::Punct(p1), TokenTree::Punct(p2), ..]
+				if p0.as_char() == ':' && p0.spacing() == Spacing::Joint && p1.as_char() == ':' && p2.as_char() == '<' =>
+			{
+				i = generics_end(tokens, i)?
+			}
+			_ => return Some(i),
+		}
+	}
+}
+
+/// Returns the end of the turbofish `::<...>` starting at `start`.
+fn generics_end(tokens: &[TokenTree], start: usize) -> Option<usize> {
+	let [TokenTree::Punct(p0), TokenTree::Punct(p1), TokenTree::Punct(p2), ..] = tokens.get(start..)? else {
+		return None;
+	};
+	if !(p0.as_char() == ':' && p0.spacing() == Spacing::Joint && p1.as_char() == ':' && p2.as_char() == '<') {
+		return None;
+	}
+
+	let mut depth = 1usize;
+	let mut i = start + 3;
+	while depth > 0 {
+		match tokens.get(i)? {
+			// `->` in `Fn() -> T`
+			TokenTree::Punct(p) if p.as_char() == '-' && p.spacing() == Spacing::Joint => i += 1,
+			TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+			TokenTree::Punct(p) if p.as_char() == '>' => depth -= 1,
+			_ => {}
+		}
+		i += 1;
+	}
+	Some(i)
 }
 
 #[proc_macro]
 pub fn assert(item: TokenStream) -> TokenStream {
+	expand(item, "do_panic")
+}
+
+#[proc_macro]
+pub fn check(item: TokenStream) -> TokenStream {
+	expand(item, "do_check")
+}
+
+#[proc_macro]
+pub fn assume(item: TokenStream) -> TokenStream {
+	expand(item, "assume")
+}
+
+fn expand(item: TokenStream, sink: &str) -> TokenStream {
 	let mut item = item.into_iter();
-	let Some(TokenTree::Group(krate)) = item.next() else { panic!() };
+	let Some(TokenTree::Group(krate)) = item.next() else {
+		return Error::new(
+			Span::call_site(),
+			"this macro is an implementation detail, use `equator::assert!` instead",
+		)
+		.into_compile_error();
+	};
 	let item = &*Vec::from_iter(item);
-	let (cond, next) = parse(item);
+	if sink == "assume" {
+		return expand_assume(&krate, item).unwrap_or_else(Error::into_compile_error);
+	}
+	if let [TokenTree::Ident(l), TokenTree::Punct(eq), ..] = item {
+		// `level == x` is a comparison
+		if l.to_string() == "level" && eq.as_char() == '=' && eq.spacing() == Spacing::Alone {
+			return expand_level(&krate, item, sink).unwrap_or_else(Error::into_compile_error);
+		}
+	}
+	if let Some(TokenTree::Ident(l)) = item.first() {
+		if l.to_string() == "let" {
+			return expand_let(&krate, item, sink).unwrap_or_else(Error::into_compile_error);
+		}
+	}
+	if let [TokenTree::Ident(kind), TokenTree::Group(g), rest @ ..] = item {
+		let whole = match rest {
+			[] => true,
+			[TokenTree::Punct(p), ..] => p.as_char() == ',',
+			_ => false,
+		};
+		if whole && sink == "do_panic" && UNWRAP.contains(&&*kind.to_string()) && g.delimiter() == Delimiter::Parenthesis && !g.stream().is_empty() {
+			return expand_unwrap(&krate, item, kind, g);
+		}
+	}
+	expand_cond(&krate, item, sink)
+}
+
+/// Expands `cond, fmt...` into a call to `sink`.
+fn expand_cond(krate: &Group, item: &[TokenTree], sink: &str) -> TokenStream {
+	let (cond, next) = match parse(item, Span::call_site()) {
+		Ok(parsed) => parsed,
+		Err(e) => return e.into_compile_error(),
+	};
+
+	// in power-assert mode, the recorded values are passed to the sink along with the condition
+	let (sink, log) = if POWER {
+		(
+			format!("{sink}_power"),
+			vec![
+				TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+				TokenTree::Ident(Ident::new("__equator_log", Span::mixed_site())),
+			],
+		)
+	} else {
+		(sink.to_owned(), vec![])
+	};
+
+	let test = TokenStream::from_iter(krate.stream().into_iter().chain([
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("assert_imp", Span::call_site())),
+		TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+		TokenTree::Group(Group::new(Delimiter::Parenthesis, cond.clone())),
+	]));
+	let stream = call_sink(krate, &sink, cond, test, &item[next..], log);
+
+	if !POWER {
+		return stream;
+	}
+	// `{ let log = $krate::power::Log::new(); $krate::sink(...) }`
+	TokenStream::from_iter([TokenTree::Group(Group::new(
+		Delimiter::Brace,
+		TokenStream::from_iter(
+			[
+				TokenTree::Ident(Ident::new("let", Span::call_site())),
+				TokenTree::Ident(Ident::new("__equator_log", Span::mixed_site())),
+				TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+			]
+			.into_iter()
+			.chain(krate.stream())
+			.chain([
+				TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+				TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+				TokenTree::Ident(Ident::new("power", Span::call_site())),
+				TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+				TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+				TokenTree::Ident(Ident::new("Log", Span::call_site())),
+				TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+				TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+				TokenTree::Ident(Ident::new("new", Span::call_site())),
+				paren(TokenStream::new()),
+				TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+			])
+			.chain(stream),
+		),
+	))])
+}
+
+/// Assertion levels, which are enabled by `debug_assertions` for `debug`, and by
+/// `$krate::level::<level>::ENABLED` for the others.
+const LEVELS: &[&str] = &["debug", "expensive", "paranoid"];
+
+/// Expands `level = name; cond, fmt...` into `if enabled { ... }`, so that the condition is still
+/// type-checked when the level is disabled.
+fn expand_level(krate: &Group, tokens: &[TokenTree], sink: &str) -> Result<TokenStream, Error> {
+	let usage = format!("expected `level = name; cond`, where `name` is one of {}", LEVELS.join(", "));
+	let [_, _, TokenTree::Ident(level), TokenTree::Punct(semi), rest @ ..] = tokens else {
+		return Err(Error::new(tokens[1].span(), &usage));
+	};
+	let level_str = level.to_string();
+	if !LEVELS.contains(&&*level_str) || semi.as_char() != ';' {
+		return Err(Error::new(level.span(), &usage));
+	}
+	match rest.first() {
+		None => return Err(Error::new(semi.span(), "expected a condition after the level")),
+		Some(TokenTree::Ident(l)) if l.to_string() == "let" => {
+			return Err(Error::new(
+				l.span(),
+				"`let pattern = value` cannot be used with a level, since its bindings would not always exist",
+			));
+		}
+		_ => {}
+	}
+
+	let enabled = if level_str == "debug" {
+		debug_assertions()
+	} else {
+		TokenStream::from_iter(krate.stream().into_iter().chain([
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("level", Span::call_site())),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(level.clone()),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("ENABLED", Span::call_site())),
+		]))
+	};
+	// a disabled `check!` succeeds
+	let disabled = if sink == "do_check" {
+		TokenStream::from_iter([
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("core", Span::call_site())),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("result", Span::call_site())),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("Result", Span::call_site())),
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("Ok", Span::call_site())),
+			paren(TokenStream::from_iter([paren(TokenStream::new())])),
+		])
+	} else {
+		TokenStream::new()
+	};
 
-	let stream = TokenStream::from_iter(
+	Ok(TokenStream::from_iter(
+		[TokenTree::Ident(Ident::new("if", Span::call_site()))].into_iter().chain(enabled).chain([
+			TokenTree::Group(Group::new(Delimiter::Brace, expand_cond(krate, rest, sink))),
+			TokenTree::Ident(Ident::new("else", Span::call_site())),
+			TokenTree::Group(Group::new(Delimiter::Brace, disabled)),
+		]),
+	))
+}
+
+/// `::core::cfg!(debug_assertions)`, which is evaluated in the crate of the caller.
+fn debug_assertions() -> TokenStream {
+	TokenStream::from_iter([
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("core", Span::call_site())),
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("cfg", Span::call_site())),
+		TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+		paren(TokenStream::from_iter([TokenTree::Ident(Ident::new(
+			"debug_assertions",
+			Span::call_site(),
+		))])),
+	])
+}
+
+/// Expands `assume!(cond, fmt...)` into `if cfg!(debug_assertions) { assert } else { hints }`, where
+/// the hints are emitted by `$krate::assume_imp!`.
+fn expand_assume(krate: &Group, tokens: &[TokenTree]) -> Result<TokenStream, Error> {
+	if let Some(TokenTree::Ident(l)) = tokens.first() {
+		if matches!(&*l.to_string(), "let" | "level") {
+			return Err(Error::new(l.span(), &format!("`{l}` is not supported by `assume!`")));
+		}
+	}
+	let (cond, _) = parse(tokens, Span::call_site())?;
+
+	let hints = TokenStream::from_iter(krate.stream().into_iter().chain([
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("assume_imp", Span::call_site())),
+		TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+		paren(cond),
+		TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+	]));
+	Ok(TokenStream::from_iter(
+		[TokenTree::Ident(Ident::new("if", Span::call_site()))]
+			.into_iter()
+			.chain(debug_assertions())
+			.chain([
+				TokenTree::Group(Group::new(Delimiter::Brace, expand_cond(krate, tokens, "do_panic"))),
+				TokenTree::Ident(Ident::new("else", Span::call_site())),
+				TokenTree::Group(Group::new(Delimiter::Brace, hints)),
+			]),
+	))
+}
+
+/// Expands `let pattern = value, fmt...` into `let value = ...; let pattern = value else { ... };`,
+/// so that the bindings of the pattern remain in scope after the assertion.
+fn expand_let(krate: &Group, tokens: &[TokenTree], sink: &str) -> Result<TokenStream, Error> {
+	if sink != "do_panic" {
+		return Err(Error::new(tokens[0].span(), "`let pattern = value` is only supported by `assert!`"));
+	}
+	// `=` may only appear in the pattern as part of `..=`
+	let eq = (1..tokens.len())
+		.find(|&i| {
+			matches!(&tokens[i], TokenTree::Punct(p) if p.as_char() == '=' && p.spacing() == Spacing::Alone)
+				&& !matches!(&tokens[i - 1], TokenTree::Punct(p) if p.spacing() == Spacing::Joint)
+		})
+		.ok_or_else(|| {
+			Error::new(
+				tokens[tokens.len() - 1].span(),
+				"expected `=` after the pattern, as in `let pattern = value`",
+			)
+		})?;
+	if eq == 1 {
+		return Err(Error::new(tokens[0].span(), "expected a pattern after `let`"));
+	}
+	let pattern = &tokens[1..eq];
+	let rest = &tokens[eq + 1..];
+	let [_, _, end, next] = parse_expr(rest)?;
+	if end == 0 {
+		return Err(Error::new(tokens[eq].span(), "expected a value after `=`"));
+	}
+	let value = &rest[..end];
+
+	let name = Ident::new("__equator_value", Span::mixed_site());
+	let source = TokenStream::from_iter([
+		TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("match", Span::call_site())),
+		paren(TokenStream::from_iter(tokens[..eq + 1 + end].iter().cloned())),
+		paren(TokenStream::from_iter(value.iter().cloned())),
+		paren(TokenStream::from_iter(pattern.iter().cloned())),
+	]);
+	let test = TokenStream::from_iter(krate.stream().into_iter().chain([
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("assert_imp", Span::call_site())),
+		TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+		paren(TokenStream::from_iter([
+			TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("let_else", Span::call_site())),
+			TokenTree::Ident(name.clone()),
+		])),
+	]));
+	let panic = call_sink(krate, "do_panic_let", source, test, &rest[next..], vec![]);
+
+	Ok(TokenStream::from_iter(
+		[
+			TokenTree::Ident(Ident::new("let", Span::call_site())),
+			TokenTree::Ident(name.clone()),
+			TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+		]
+		.into_iter()
+		.chain(value.iter().cloned())
+		.chain([
+			TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+			TokenTree::Ident(Ident::new("let", Span::call_site())),
+		])
+		.chain(pattern.iter().cloned())
+		.chain([
+			TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+			TokenTree::Ident(name),
+			TokenTree::Ident(Ident::new("else", Span::call_site())),
+			TokenTree::Group(Group::new(Delimiter::Brace, panic)),
+			TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+		]),
+	))
+}
+
+/// Expands `kind(value), fmt...` into a `match` on `value` that evaluates to its payload, and panics
+/// if `value` is not of the expected variant.
+fn expand_unwrap(krate: &Group, tokens: &[TokenTree], kind: &Ident, value: &Group) -> TokenStream {
+	let name = Ident::new("__equator_value", Span::mixed_site());
+	let source = unwrap(&tokens[..2], kind, value.stream());
+	let test = TokenStream::from_iter(krate.stream().into_iter().chain([
+		TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+		TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+		TokenTree::Ident(Ident::new("assert_imp", Span::call_site())),
+		TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+		paren(unwrap(&[], kind, TokenStream::from_iter([TokenTree::Ident(name.clone())]))),
+	]));
+	let panic = call_sink(krate, "do_panic_let", source, test, tokens.get(3..).unwrap_or_default(), vec![]);
+
+	TokenStream::from_iter(
 		krate.stream().into_iter().chain([
 			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
 			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
-			TokenTree::Ident(Ident::new("do_panic", Span::call_site())),
+			TokenTree::Ident(Ident::new("assert_imp", Span::call_site())),
+			TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+			paren(TokenStream::from_iter(
+				[
+					TokenTree::Punct(Punct::new('@', Spacing::Alone)),
+					TokenTree::Ident(Ident::new("unwrap_or", Span::call_site())),
+					TokenTree::Ident(kind.clone()),
+					TokenTree::Group(value.clone()),
+					TokenTree::Ident(name),
+				]
+				.into_iter()
+				.chain(panic),
+			)),
+		]),
+	)
+}
+
+/// Builds `$krate::sink(const { &WithSource { ..., source: source_imp!(source) } }, test, fmt_imp!(fmt), extra...)`.
+fn call_sink(krate: &Group, sink: &str, source: TokenStream, test: TokenStream, fmt: &[TokenTree], extra: Vec<TokenTree>) -> TokenStream {
+	TokenStream::from_iter(
+		krate.stream().into_iter().chain([
+			TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+			TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+			TokenTree::Ident(Ident::new(sink, Span::call_site())),
 			TokenTree::Group(Group::new(
 				Delimiter::Parenthesis,
 				TokenStream::from_iter(
@@ -336,7 +1554,7 @@ pub fn assert(item: TokenStream) -> TokenStream {
 														TokenTree::Punct(Punct::new(':', Spacing::Alone)),
 														TokenTree::Ident(Ident::new("source_imp", Span::call_site())),
 														TokenTree::Punct(Punct::new('!', Spacing::Alone)),
-														TokenTree::Group(Group::new(Delimiter::Parenthesis, cond.clone())),
+														TokenTree::Group(Group::new(Delimiter::Parenthesis, source)),
 														TokenTree::Punct(Punct::new(',', Spacing::Alone)),
 													])
 													.collect(),
@@ -346,28 +1564,20 @@ pub fn assert(item: TokenStream) -> TokenStream {
 							)),
 							TokenTree::Punct(Punct::new(',', Spacing::Alone)),
 						])
-						.chain(krate.stream())
-						.chain([
-							TokenTree::Punct(Punct::new(':', Spacing::Joint)),
-							TokenTree::Punct(Punct::new(':', Spacing::Alone)),
-							TokenTree::Ident(Ident::new("assert_imp", Span::call_site())),
-							TokenTree::Punct(Punct::new('!', Spacing::Alone)),
-							TokenTree::Group(Group::new(Delimiter::Parenthesis, cond.clone())),
-							TokenTree::Punct(Punct::new(',', Spacing::Alone)),
-						])
+						.chain(test)
+						.chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))])
 						.chain(krate.stream())
 						.chain([
 							TokenTree::Punct(Punct::new(':', Spacing::Joint)),
 							TokenTree::Punct(Punct::new(':', Spacing::Alone)),
 							TokenTree::Ident(Ident::new("fmt_imp", Span::call_site())),
 							TokenTree::Punct(Punct::new('!', Spacing::Alone)),
-							TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from_iter(item[next..].iter().cloned()))),
+							TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from_iter(fmt.iter().cloned()))),
 							TokenTree::Punct(Punct::new(',', Spacing::Alone)),
-						]),
+						])
+						.chain(extra),
 				),
 			)),
 		]),
-	);
-
-	stream
+	)
 }
@@ -0,0 +1,281 @@
+//! Tolerance-based comparators for floating point values.
+//!
+//! These can be used with the custom comparator syntax, e.g.
+//! `equator::assert!(x :AbsDiff { tol: 1e-9 }: y)`, and compare arrays and slices element-wise.
+//!
+//! No tolerance makes NaN compare equal to anything, including itself. Infinities are only
+//! equal to themselves.
+
+use crate::Cmp;
+use core::fmt;
+
+/// Passes if `|lhs - rhs| <= tol`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AbsDiff {
+	pub tol: f64,
+}
+
+/// Passes if `|lhs - rhs| <= tol * max(|lhs|, |rhs|)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RelDiff {
+	pub tol: f64,
+}
+
+/// Passes if `lhs` and `rhs` are at most `max` representable values apart.
+///
+/// Values of opposite signs only compare equal if they are both zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ulps {
+	pub max: u64,
+}
+
+/// Passes if any of the absolute, relative or ULPs tolerances is satisfied.
+///
+/// The default only accepts values at most 4 ULPs apart.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Approx {
+	pub abs: f64,
+	pub rel: f64,
+	pub ulps: u64,
+}
+
+impl AbsDiff {
+	#[inline]
+	pub const fn new(tol: f64) -> Self {
+		Self { tol }
+	}
+}
+
+impl RelDiff {
+	#[inline]
+	pub const fn new(tol: f64) -> Self {
+		Self { tol }
+	}
+}
+
+impl Ulps {
+	#[inline]
+	pub const fn new(max: u64) -> Self {
+		Self { max }
+	}
+}
+
+impl Approx {
+	pub const DEFAULT: Self = Self { abs: 0.0, rel: 0.0, ulps: 4 };
+}
+
+impl Default for Approx {
+	#[inline]
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+trait Float: Copy + PartialEq {
+	fn to_f64(self) -> f64;
+	fn is_nan(self) -> bool;
+	fn is_infinite(self) -> bool;
+	fn is_sign_negative(self) -> bool;
+	/// Position of `self` among the non-negative values of its type, ignoring the sign.
+	fn abs_bits(self) -> u64;
+}
+
+macro_rules! impl_float {
+	($($ty: ty),*) => {$(
+		impl Float for $ty {
+			#[inline]
+			fn to_f64(self) -> f64 {
+				self as f64
+			}
+			#[inline]
+			fn is_nan(self) -> bool {
+				<$ty>::is_nan(self)
+			}
+			#[inline]
+			fn is_infinite(self) -> bool {
+				<$ty>::is_infinite(self)
+			}
+			#[inline]
+			fn is_sign_negative(self) -> bool {
+				<$ty>::is_sign_negative(self)
+			}
+			#[inline]
+			fn abs_bits(self) -> u64 {
+				(<$ty>::abs(self)).to_bits() as u64
+			}
+		}
+	)*};
+}
+impl_float!(f32, f64);
+
+#[inline]
+fn abs_diff<T: Float>(tol: f64, lhs: T, rhs: T) -> bool {
+	// exact equality also covers matching infinities, whose difference is NaN
+	lhs == rhs || (!lhs.is_infinite() && !rhs.is_infinite() && f64::abs(lhs.to_f64() - rhs.to_f64()) <= tol)
+}
+
+#[inline]
+fn rel_diff<T: Float>(tol: f64, lhs: T, rhs: T) -> bool {
+	let (l, r) = (lhs.to_f64(), rhs.to_f64());
+	lhs == rhs || (!lhs.is_infinite() && !rhs.is_infinite() && f64::abs(l - r) <= tol * f64::max(f64::abs(l), f64::abs(r)))
+}
+
+#[inline]
+fn ulps<T: Float>(max: u64, lhs: T, rhs: T) -> bool {
+	if lhs == rhs {
+		return true;
+	}
+	if lhs.is_nan() || rhs.is_nan() || lhs.is_infinite() || rhs.is_infinite() || lhs.is_sign_negative() != rhs.is_sign_negative() {
+		return false;
+	}
+	lhs.abs_bits().abs_diff(rhs.abs_bits()) <= max
+}
+
+/// Explains the failures shared by every comparator, if any.
+fn explain_special<T: Float>(lhs: T, rhs: T, f: &mut fmt::Formatter) -> Option<fmt::Result> {
+	if lhs.is_nan() || rhs.is_nan() {
+		Some(f.write_str("NaN is not equal to any value"))
+	} else if lhs.is_infinite() || rhs.is_infinite() {
+		Some(f.write_str("infinite values are only equal to themselves"))
+	} else {
+		None
+	}
+}
+
+fn explain_abs_diff<T: Float>(tol: f64, lhs: T, rhs: T, f: &mut fmt::Formatter) -> fmt::Result {
+	let diff = f64::abs(lhs.to_f64() - rhs.to_f64());
+	write!(f, "difference {diff} exceeds tolerance {tol}")
+}
+
+fn explain_rel_diff<T: Float>(tol: f64, lhs: T, rhs: T, f: &mut fmt::Formatter) -> fmt::Result {
+	let (l, r) = (lhs.to_f64(), rhs.to_f64());
+	let diff = f64::abs(l - r) / f64::max(f64::abs(l), f64::abs(r));
+	write!(f, "relative difference {diff} exceeds tolerance {tol}")
+}
+
+fn explain_ulps<T: Float>(max: u64, lhs: T, rhs: T, f: &mut fmt::Formatter) -> fmt::Result {
+	if lhs.is_sign_negative() != rhs.is_sign_negative() {
+		f.write_str("values have opposite signs")
+	} else {
+		write!(f, "values are {} ULPs apart, more than {max}", lhs.abs_bits().abs_diff(rhs.abs_bits()))
+	}
+}
+
+macro_rules! impl_cmp {
+	($($ty: ty),*) => {$(
+		impl Cmp<$ty, $ty> for AbsDiff {
+			#[inline]
+			fn test(&self, lhs: &$ty, rhs: &$ty) -> bool {
+				abs_diff(self.tol, *lhs, *rhs)
+			}
+
+			fn explain(&self, lhs: &$ty, rhs: &$ty, f: &mut fmt::Formatter) -> fmt::Result {
+				explain_special(*lhs, *rhs, f).unwrap_or_else(|| explain_abs_diff(self.tol, *lhs, *rhs, f))
+			}
+		}
+		impl Cmp<$ty, $ty> for RelDiff {
+			#[inline]
+			fn test(&self, lhs: &$ty, rhs: &$ty) -> bool {
+				rel_diff(self.tol, *lhs, *rhs)
+			}
+
+			fn explain(&self, lhs: &$ty, rhs: &$ty, f: &mut fmt::Formatter) -> fmt::Result {
+				explain_special(*lhs, *rhs, f).unwrap_or_else(|| explain_rel_diff(self.tol, *lhs, *rhs, f))
+			}
+		}
+		impl Cmp<$ty, $ty> for Ulps {
+			#[inline]
+			fn test(&self, lhs: &$ty, rhs: &$ty) -> bool {
+				ulps(self.max, *lhs, *rhs)
+			}
+
+			fn explain(&self, lhs: &$ty, rhs: &$ty, f: &mut fmt::Formatter) -> fmt::Result {
+				explain_special(*lhs, *rhs, f).unwrap_or_else(|| explain_ulps(self.max, *lhs, *rhs, f))
+			}
+		}
+		impl Cmp<$ty, $ty> for Approx {
+			#[inline]
+			fn test(&self, lhs: &$ty, rhs: &$ty) -> bool {
+				let (lhs, rhs) = (*lhs, *rhs);
+				abs_diff(self.abs, lhs, rhs) || rel_diff(self.rel, lhs, rhs) || ulps(self.ulps, lhs, rhs)
+			}
+
+			fn explain(&self, lhs: &$ty, rhs: &$ty, f: &mut fmt::Formatter) -> fmt::Result {
+				let (lhs, rhs) = (*lhs, *rhs);
+				explain_special(lhs, rhs, f).unwrap_or_else(|| {
+					explain_abs_diff(self.abs, lhs, rhs, f)?;
+					f.write_str("\n")?;
+					explain_rel_diff(self.rel, lhs, rhs, f)?;
+					f.write_str("\n")?;
+					explain_ulps(self.ulps, lhs, rhs, f)
+				})
+			}
+		}
+	)*};
+}
+impl_cmp!(f32, f64);
+
+macro_rules! impl_cmp_seq {
+	($($cmp: ty),*) => {$(
+		impl<T, U> Cmp<[T], [U]> for $cmp
+		where
+			$cmp: Cmp<T, U>,
+		{
+			#[inline]
+			fn test(&self, lhs: &[T], rhs: &[U]) -> bool {
+				lhs.len() == rhs.len() && core::iter::zip(lhs, rhs).all(|(lhs, rhs)| self.test(lhs, rhs))
+			}
+
+			fn explain(&self, lhs: &[T], rhs: &[U], f: &mut fmt::Formatter) -> fmt::Result {
+				if lhs.len() != rhs.len() {
+					return write!(f, "lengths differ: {} != {}", lhs.len(), rhs.len());
+				}
+				match core::iter::zip(lhs, rhs).position(|(lhs, rhs)| !self.test(lhs, rhs)) {
+					Some(i) => {
+						write!(f, "first mismatch at index {i}: ")?;
+						self.explain(&lhs[i], &rhs[i], f)
+					}
+					None => Ok(()),
+				}
+			}
+		}
+		// unsized operands are compared through a reference by the assertion macros
+		impl<T, U> Cmp<&[T], &[U]> for $cmp
+		where
+			$cmp: Cmp<T, U>,
+		{
+			#[inline]
+			fn test(&self, lhs: &&[T], rhs: &&[U]) -> bool {
+				Cmp::<[T], [U]>::test(self, lhs, rhs)
+			}
+
+			fn explain(&self, lhs: &&[T], rhs: &&[U], f: &mut fmt::Formatter) -> fmt::Result {
+				Cmp::<[T], [U]>::explain(self, lhs, rhs, f)
+			}
+		}
+		impl<T, U, const N: usize> Cmp<[T; N], [U; N]> for $cmp
+		where
+			$cmp: Cmp<T, U>,
+		{
+			#[inline]
+			fn test(&self, lhs: &[T; N], rhs: &[U; N]) -> bool {
+				Cmp::<[T], [U]>::test(self, lhs, rhs)
+			}
+
+			fn explain(&self, lhs: &[T; N], rhs: &[U; N], f: &mut fmt::Formatter) -> fmt::Result {
+				Cmp::<[T], [U]>::explain(self, lhs, rhs, f)
+			}
+		}
+	)*};
+}
+impl_cmp_seq!(AbsDiff, RelDiff, Ulps, Approx);
+
+/// Default comparator of `lhs ~ rhs`, which stands for [`Approx::DEFAULT`] and accepts values at
+/// most 4 ULPs apart.
+///
+/// `lhs ~ rhs` uses whichever `approx_eq` is in scope. This is a function rather than a constant, so
+/// that a local variable named `approx_eq` still takes precedence over it once imported.
+#[inline]
+pub fn approx_eq() -> Approx {
+	Approx::DEFAULT
+}
@@ -0,0 +1,204 @@
+//! Ranges and collections that can be searched with `item in collection`.
+//!
+//! Ranges are reported with their bounds, and other collections with their first
+//! [`TRUNCATE`] elements.
+
+use core::{
+	fmt,
+	ops::{Bound, Range, RangeBounds, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive},
+};
+
+/// Number of elements of a collection that are listed in failure reports.
+pub const TRUNCATE: usize = 10;
+
+/// Collection that can be formatted as the right-hand side of `item in collection`.
+pub trait Collection {
+	/// Formats the collection for a failure report, which should be kept short.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Collection that can be searched for an item with `item in collection`.
+pub trait Contains<T: ?Sized>: Collection {
+	fn contains(&self, item: &T) -> bool;
+
+	/// Explains why `item` was not found, as in [`Cmp::explain`](crate::Cmp::explain).
+	#[inline(always)]
+	fn explain(&self, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+		let _ = (item, f);
+		Ok(())
+	}
+}
+
+/// Placeholder for the items of a collection that are left out of a failure report.
+struct More(usize);
+
+impl fmt::Debug for More {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "... and {} more", self.0)
+	}
+}
+
+impl<U: fmt::Debug> Collection for [U] {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut list = f.debug_list();
+		list.entries(self.iter().take(TRUNCATE));
+		if self.len() > TRUNCATE {
+			list.entry(&More(self.len() - TRUNCATE));
+		}
+		list.finish()
+	}
+}
+
+impl<T: ?Sized + PartialEq<U>, U: fmt::Debug> Contains<T> for [U] {
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		self.iter().any(|x| *item == *x)
+	}
+
+	fn explain(&self, _: &T, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.len() > TRUNCATE {
+			write!(f, "not found among {} elements", self.len())?;
+		}
+		Ok(())
+	}
+}
+
+impl<U: fmt::Debug, const N: usize> Collection for [U; N] {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Collection::fmt(self.as_slice(), f)
+	}
+}
+
+impl<T: ?Sized + PartialEq<U>, U: fmt::Debug, const N: usize> Contains<T> for [U; N] {
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		Contains::contains(self.as_slice(), item)
+	}
+
+	#[inline]
+	fn explain(&self, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+		Contains::explain(self.as_slice(), item, f)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<U: fmt::Debug> Collection for alloc::vec::Vec<U> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Collection::fmt(self.as_slice(), f)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + PartialEq<U>, U: fmt::Debug> Contains<T> for alloc::vec::Vec<U> {
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		Contains::contains(self.as_slice(), item)
+	}
+
+	#[inline]
+	fn explain(&self, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+		Contains::explain(self.as_slice(), item, f)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<U: fmt::Debug> Collection for alloc::collections::BTreeSet<U> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut set = f.debug_set();
+		set.entries(self.iter().take(TRUNCATE));
+		if self.len() > TRUNCATE {
+			set.entry(&More(self.len() - TRUNCATE));
+		}
+		set.finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized + Ord, U: core::borrow::Borrow<T> + Ord + fmt::Debug> Contains<T> for alloc::collections::BTreeSet<U> {
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		self.contains(item)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<U: fmt::Debug, S> Collection for std::collections::HashSet<U, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut set = f.debug_set();
+		set.entries(self.iter().take(TRUNCATE));
+		if self.len() > TRUNCATE {
+			set.entry(&More(self.len() - TRUNCATE));
+		}
+		set.finish()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized + core::hash::Hash + Eq, U: core::borrow::Borrow<T> + core::hash::Hash + Eq + fmt::Debug, S: core::hash::BuildHasher> Contains<T>
+	for std::collections::HashSet<U, S>
+{
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		self.contains(item)
+	}
+}
+
+impl<R: ?Sized + Collection> Collection for &R {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		(**self).fmt(f)
+	}
+}
+
+impl<T: ?Sized, R: ?Sized + Contains<T>> Contains<T> for &R {
+	#[inline]
+	fn contains(&self, item: &T) -> bool {
+		(**self).contains(item)
+	}
+
+	#[inline]
+	fn explain(&self, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+		(**self).explain(item, f)
+	}
+}
+
+/// Explains which bound of `range` excludes `item`.
+fn explain_bounds<T: ?Sized + PartialOrd<U>, U: fmt::Debug>(range: &impl RangeBounds<U>, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+	match range.start_bound() {
+		Bound::Included(start) if *item < *start => return write!(f, "below the lower bound {start:?}"),
+		Bound::Excluded(start) if *item <= *start => return write!(f, "not above the exclusive lower bound {start:?}"),
+		_ => {}
+	}
+	match range.end_bound() {
+		Bound::Included(end) if *item > *end => write!(f, "above the upper bound {end:?}"),
+		Bound::Excluded(end) if *item >= *end => write!(f, "not below the exclusive upper bound {end:?}"),
+		_ => f.write_str("not comparable to the bounds"),
+	}
+}
+
+macro_rules! impl_range {
+	($($range:ident),*) => {$(
+		impl<U: fmt::Debug> Collection for $range<U> {
+			#[inline]
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Debug::fmt(self, f)
+			}
+		}
+
+		impl<T: ?Sized + PartialOrd<U>, U: PartialOrd<T> + fmt::Debug> Contains<T> for $range<U> {
+			#[inline]
+			fn contains(&self, item: &T) -> bool {
+				RangeBounds::contains(self, item)
+			}
+
+			#[inline]
+			fn explain(&self, item: &T, f: &mut fmt::Formatter) -> fmt::Result {
+				explain_bounds(self, item, f)
+			}
+		}
+	)*};
+}
+
+impl_range!(Range, RangeInclusive, RangeFrom, RangeTo, RangeToInclusive);
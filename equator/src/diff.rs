@@ -0,0 +1,141 @@
+use crate::Style;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Number of unchanged lines printed around each change.
+const CONTEXT: usize = 3;
+
+/// Number of changed lines printed, beyond which the diff is truncated.
+const MAX_CHANGES: usize = 32;
+
+/// Largest table the line matching is allowed to allocate. Beyond that, the differing regions are
+/// reported as a whole.
+const MAX_TABLE: usize = 1 << 22;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Op {
+	Equal,
+	Delete,
+	Insert,
+}
+
+/// Computes an edit script from `lhs` to `rhs`, as a list of operations along with the lhs and rhs
+/// line index they apply to.
+fn edit_script(lhs: &[&str], rhs: &[&str]) -> Vec<(Op, usize, usize)> {
+	let prefix = lhs.iter().zip(rhs).take_while(|(l, r)| l == r).count();
+	let suffix = lhs[prefix..].iter().rev().zip(rhs[prefix..].iter().rev()).take_while(|(l, r)| l == r).count();
+
+	let l = &lhs[prefix..lhs.len() - suffix];
+	let r = &rhs[prefix..rhs.len() - suffix];
+
+	let mut script = Vec::with_capacity(lhs.len() + rhs.len());
+	script.extend((0..prefix).map(|i| (Op::Equal, i, i)));
+
+	let (n, m) = (l.len(), r.len());
+	if (n + 1).saturating_mul(m + 1) <= MAX_TABLE {
+		// lcs[i * (m + 1) + j] is the length of the longest common subsequence of l[i..] and r[j..]
+		let mut lcs = alloc::vec![0u32; (n + 1) * (m + 1)];
+		for i in (0..n).rev() {
+			for j in (0..m).rev() {
+				lcs[i * (m + 1) + j] = if l[i] == r[j] {
+					lcs[(i + 1) * (m + 1) + j + 1] + 1
+				} else {
+					Ord::max(lcs[(i + 1) * (m + 1) + j], lcs[i * (m + 1) + j + 1])
+				};
+			}
+		}
+
+		let (mut i, mut j) = (0, 0);
+		while i < n || j < m {
+			if i < n && j < m && l[i] == r[j] {
+				script.push((Op::Equal, prefix + i, prefix + j));
+				i += 1;
+				j += 1;
+			} else if j == m || (i < n && lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1]) {
+				script.push((Op::Delete, prefix + i, prefix + j));
+				i += 1;
+			} else {
+				script.push((Op::Insert, prefix + i, prefix + j));
+				j += 1;
+			}
+		}
+	} else {
+		script.extend((0..n).map(|i| (Op::Delete, prefix + i, prefix)));
+		script.extend((0..m).map(|j| (Op::Insert, prefix + n, prefix + j)));
+	}
+
+	script.extend((0..suffix).map(|k| (Op::Equal, prefix + n + k, prefix + m + k)));
+	script
+}
+
+/// Writes a unified diff of the lines of `lhs` and `rhs`, truncated after [`MAX_CHANGES`] changed
+/// lines.
+pub(crate) fn write_diff(f: &mut fmt::Formatter<'_>, lhs_name: &str, rhs_name: &str, lhs: &str, rhs: &str) -> fmt::Result {
+	let lhs = Vec::from_iter(lhs.lines());
+	let rhs = Vec::from_iter(rhs.lines());
+	let script = edit_script(&lhs, &rhs);
+
+	Style::RED.paint(f, |f| write!(f, "--- {lhs_name}"))?;
+	f.write_str("\n")?;
+	Style::GREEN.paint(f, |f| write!(f, "+++ {rhs_name}"))?;
+
+	let mut start = 0;
+	let mut changes = 0;
+	while let Some(first_change) = script[start..].iter().position(|&(op, _, _)| op != Op::Equal) {
+		let first_change = start + first_change;
+
+		// extend the hunk until the next change is too far away to share context with this one, or
+		// until enough changes were printed
+		let mut end = first_change;
+		let mut last_change = first_change;
+		while end < script.len() && end - last_change <= 2 * CONTEXT && changes < MAX_CHANGES {
+			if script[end].0 != Op::Equal {
+				last_change = end;
+				changes += 1;
+			}
+			end += 1;
+		}
+
+		// the changes that are not printed are counted instead, and not shown as context either
+		let more = match changes {
+			MAX_CHANGES => script[last_change + 1..].iter().filter(|&&(op, _, _)| op != Op::Equal).count(),
+			_ => 0,
+		};
+		let begin = first_change.saturating_sub(CONTEXT).max(start);
+		let end = match more {
+			0 => Ord::min(last_change + 1 + CONTEXT, script.len()),
+			_ => last_change + 1,
+		};
+		let hunk = &script[begin..end];
+
+		let lhs_len = hunk.iter().filter(|&&(op, _, _)| op != Op::Insert).count();
+		let rhs_len = hunk.iter().filter(|&&(op, _, _)| op != Op::Delete).count();
+		let (_, lhs_start, rhs_start) = hunk[0];
+		write!(
+			f,
+			"\n@@ -{},{lhs_len} +{},{rhs_len} @@",
+			lhs_start + (lhs_len > 0) as usize,
+			rhs_start + (rhs_len > 0) as usize,
+		)?;
+
+		for &(op, i, j) in hunk {
+			match op {
+				Op::Equal => write!(f, "\n {}", lhs[i])?,
+				Op::Delete => {
+					f.write_str("\n")?;
+					Style::RED.paint(f, |f| write!(f, "-{}", lhs[i]))?
+				},
+				Op::Insert => {
+					f.write_str("\n")?;
+					Style::GREEN.paint(f, |f| write!(f, "+{}", rhs[j]))?
+				},
+			}
+		}
+
+		if more > 0 {
+			return write!(f, "\n... and {more} more changed lines");
+		}
+		start = end;
+	}
+	Ok(())
+}
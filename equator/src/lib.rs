@@ -1,7 +1,59 @@
 #![no_std]
+#![allow(clippy::missing_transmute_annotations, clippy::wrong_self_convention)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use core::fmt;
 
+pub mod cmp;
+pub mod contains;
+mod style;
+
+#[cfg(feature = "diff")]
+mod diff;
+
+#[cfg(feature = "alloc")]
+mod report;
+#[cfg(feature = "alloc")]
+pub use report::{Operand, Report, ReportKind};
+
+#[cfg(feature = "power")]
+#[doc(hidden)]
+pub mod power;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod quant;
+
+/// Assertion levels, which gate the assertions written as `assert!(level = name; cond)`.
+///
+/// The `debug` level is enabled with `debug_assertions`, like [`debug_assert!`]. The other levels are
+/// enabled by the feature of the same name, or by `--cfg equator_level="name"`, and each of them also
+/// enables the levels below it. Disabled assertions are still type-checked, but compile to nothing.
+pub mod level {
+	/// Invariants that are too slow for debug builds, but can be tested in CI.
+	pub mod expensive {
+		/// Whether `level = expensive` assertions are tested.
+		pub const ENABLED: bool = cfg!(any(feature = "expensive", equator_level = "expensive")) || super::paranoid::ENABLED;
+	}
+
+	/// Invariants that are only worth testing when hunting for a bug.
+	pub mod paranoid {
+		/// Whether `level = paranoid` assertions are tested.
+		pub const ENABLED: bool = cfg!(any(feature = "paranoid", equator_level = "paranoid"));
+	}
+}
+
+#[cfg(feature = "std")]
+mod soft;
+#[cfg(feature = "std")]
+pub use soft::{soft, SoftGuard};
+
+use style::Style;
+
 #[doc(hidden)]
 pub use equator_macro as imp;
 
@@ -25,16 +77,158 @@ macro_rules! source_imp {
 	(all(($($head:tt)*))) => { $crate::source_imp!($($head)*) };
 	(any(($($head:tt)*))) => { $crate::source_imp!($($head)*) };
 
+	(all(($($head:tt)*) $rhs:tt)) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($head)*),
+			rhs: &$crate::source_imp!(all($rhs)),
+			tail: false,
+		}}
+	};
 	(all(($($head:tt)*) $($tail:tt)+)) => {
-		const{$crate::expr::AndExpr {
+		const{$crate::expr::ListSource {
 			lhs: &$crate::source_imp!($($head)*),
 			rhs: &$crate::source_imp!(all($($tail)+)),
+			tail: true,
+		}}
+	};
+	(any(($($head:tt)*) $rhs:tt)) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($head)*),
+			rhs: &$crate::source_imp!(any($rhs)),
+			tail: false,
 		}}
 	};
 	(any(($($head:tt)*) $($tail:tt)+)) => {
-		const{$crate::expr::OrExpr {
+		const{$crate::expr::ListSource {
 			lhs: &$crate::source_imp!($($head)*),
 			rhs: &$crate::source_imp!(any($($tail)+)),
+			tail: true,
+		}}
+	};
+
+	(@and ($($lhs:tt)*) ($($source:tt)*) ($($rhs:tt)*)) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($lhs)*),
+			rhs: &$crate::expr::LazySource {
+				source: ::core::stringify!($($source)*),
+				inner: &$crate::source_imp!($($rhs)*),
+			},
+			tail: false,
+		}}
+	};
+	(@and ($($lhs:tt)*) ($($source:tt)*) $rhs:tt $(($($rest:tt)*) $rest_rhs:tt)+) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($lhs)*),
+			rhs: &$crate::expr::LazySource {
+				source: ::core::concat!(::core::stringify!($($source)*) $(, " && ", ::core::stringify!($($rest)*))+),
+				inner: &$crate::source_imp!(@and $rhs $(($($rest)*) $rest_rhs)+),
+			},
+			tail: true,
+		}}
+	};
+	(@or ($($lhs:tt)*) ($($source:tt)*) ($($rhs:tt)*)) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($lhs)*),
+			rhs: &$crate::expr::LazySource {
+				source: ::core::stringify!($($source)*),
+				inner: &$crate::source_imp!($($rhs)*),
+			},
+			tail: false,
+		}}
+	};
+	(@or ($($lhs:tt)*) ($($source:tt)*) $rhs:tt $(($($rest:tt)*) $rest_rhs:tt)+) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($lhs)*),
+			rhs: &$crate::expr::LazySource {
+				source: ::core::concat!(::core::stringify!($($source)*) $(, " || ", ::core::stringify!($($rest)*))+),
+				inner: &$crate::source_imp!(@or $rhs $(($($rest)*) $rest_rhs)+),
+			},
+			tail: true,
+		}}
+	};
+
+	(not(($($inner:tt)*))) => {
+		const{$crate::expr::NotExpr {
+			inner: &$crate::source_imp!($($inner)*),
+		}}
+	};
+
+	(implies(($($lhs:tt)*) (@lazy ($($source:tt)*) ($($rhs:tt)*)))) => {
+		const{$crate::expr::ImpliesExpr {
+			lhs: &$crate::source_imp!($($lhs)*),
+			rhs: &$crate::expr::LazySource {
+				source: ::core::stringify!($($source)*),
+				inner: &$crate::source_imp!($($rhs)*),
+			},
+		}}
+	};
+
+	(@call ($($call:tt)*) ($($recv:tt)*) $kind:ident $method:tt ($(($($capture:tt)*) ($($arg:tt)*))*)) => {
+		const{$crate::expr::CallExpr {
+			result: ::core::stringify!($($call)*),
+			recv: ::core::stringify!($($recv)*),
+			args: &[$(::core::stringify!($($arg)*)),*] as &[&str],
+		}}
+	};
+
+	(@power ($($source:tt)*) $places:tt $imp:tt) => { $crate::source_imp!($($source)*) };
+
+	(@match ($($source:tt)*) ($($value:tt)*) $pattern:tt) => {
+		const{$crate::expr::MatchExpr {
+			result: ::core::stringify!($($source)*),
+			value: ::core::stringify!($($value)*),
+		}}
+	};
+
+	(@chain ($($lop:tt)*) ($($rop:tt)*) ($lhs:expr, $mid:expr, $rhs:expr)) => {
+		const{$crate::expr::ChainExpr {
+			lhs: $crate::expr::CmpExpr {
+				cmp: ::core::stringify!($($lop)*),
+				lhs: ::core::stringify!($lhs),
+				rhs: ::core::stringify!($mid),
+			},
+			rhs: $crate::expr::CmpExpr {
+				cmp: ::core::stringify!($($rop)*),
+				lhs: ::core::stringify!($mid),
+				rhs: ::core::stringify!($rhs),
+			},
+		}}
+	};
+
+	(@unwrap ($($source:tt)*) $kind:ident ($($value:tt)*)) => {
+		const{$crate::expr::MatchExpr {
+			result: ::core::stringify!($($source)*),
+			value: ::core::stringify!($($value)*),
+		}}
+	};
+
+	(@quant ($($source:tt)*) $quantifier:ident $var:tt $iter:tt $cond:tt) => { ::core::stringify!($($source)*) };
+
+	(@message $fmt:tt ($($inner:tt)*)) => { $crate::source_imp!($($inner)*) };
+
+	(@label $label:literal ($($inner:tt)*)) => {
+		const{$crate::expr::LabelExpr {
+			label: $label,
+			inner: &$crate::source_imp!($($inner)*),
+		}}
+	};
+
+	(@list) => { () };
+	(@list ($($head:tt)*) $($tail:tt)*) => {
+		const{$crate::expr::ListSource {
+			lhs: &$crate::source_imp!($($head)*),
+			rhs: &$crate::source_imp!(@list $($tail)*),
+			tail: true,
+		}}
+	};
+	(at_least(($($n:tt)*) $($list:tt)*)) => { $crate::source_imp!(@count ($($n)*) $($list)*) };
+	(at_most(($($n:tt)*) $($list:tt)*)) => { $crate::source_imp!(@count ($($n)*) $($list)*) };
+	(exactly(($($n:tt)*) $($list:tt)*)) => { $crate::source_imp!(@count ($($n)*) $($list)*) };
+	(@count ($($n:tt)*) $($list:tt)*) => {
+		const{$crate::expr::CountExpr {
+			kind: (),
+			n: ::core::stringify!($($n)*),
+			list: &$crate::source_imp!(@list $($list)*),
 		}}
 	};
 
@@ -80,8 +274,15 @@ macro_rules! source_imp {
 			rhs: ::core::stringify!($rhs),
 		}}
 	};
-	($test:expr,($lhs: expr, $rhs: expr)) => {const{$crate::expr::CmpExpr {
-			cmp: ::core::stringify!($test),
+	(in($lhs: expr, $rhs: expr)) => {
+		const{$crate::expr::CmpExpr {
+			cmp: "in",
+			lhs: ::core::stringify!($lhs),
+			rhs: ::core::stringify!($rhs),
+		}}
+	};
+	(~($tol:expr), ($lhs: expr, $rhs: expr)) => {const{$crate::expr::CmpExpr {
+			cmp: ::core::concat!("~(", ::core::stringify!($tol), ")"),
 			lhs: ::core::stringify!($lhs),
 			rhs: ::core::stringify!($rhs),
 		}}};
@@ -90,6 +291,11 @@ macro_rules! source_imp {
 			lhs: ::core::stringify!($lhs),
 			rhs: ::core::stringify!($rhs),
 		}}};
+	($test:expr,($lhs: expr, $rhs: expr)) => {const{$crate::expr::CmpExpr {
+			cmp: ::core::stringify!($test),
+			lhs: ::core::stringify!($lhs),
+			rhs: ::core::stringify!($rhs),
+		}}};
 	($cond:expr) => { ::core::stringify!($cond) };
 }
 
@@ -115,6 +321,224 @@ macro_rules! assert_imp {
 		}
 	};
 
+	// the right operand is only evaluated if the left one did not decide the result. it is built in
+	// the tail of a block, so that its temporaries live as long as those of the whole condition
+	(@and ($($lhs:tt)*) $source:tt $rhs:tt $($rest:tt)*) => {{
+		let mut held = false;
+		$crate::expr::LazyAndExpr {
+			lhs: $crate::expr::tested($crate::assert_imp!($($lhs)*), &mut held),
+			rhs: 'rhs: {
+				if !held {
+					break 'rhs ::core::option::Option::None;
+				}
+				::core::option::Option::Some($crate::assert_imp!(@and $rhs $($rest)*))
+			},
+		}
+	}};
+	(@or ($($lhs:tt)*) $source:tt $rhs:tt $($rest:tt)*) => {{
+		let mut held = false;
+		$crate::expr::LazyOrExpr {
+			lhs: $crate::expr::tested($crate::assert_imp!($($lhs)*), &mut held),
+			rhs: 'rhs: {
+				if held {
+					break 'rhs ::core::option::Option::None;
+				}
+				::core::option::Option::Some($crate::assert_imp!(@or $rhs $($rest)*))
+			},
+		}
+	}};
+	(@and ($($cond:tt)*)) => { $crate::assert_imp!($($cond)*) };
+	(@or ($($cond:tt)*)) => { $crate::assert_imp!($($cond)*) };
+
+	(not(($($inner:tt)*))) => {
+		$crate::expr::NotExpr {
+			inner: $crate::assert_imp!($($inner)*),
+		}
+	};
+
+	// the conclusion is only evaluated if the premise holds, like the right operand of `&&`
+	(implies(($($lhs:tt)*) (@lazy $source:tt ($($rhs:tt)*)))) => {{
+		let mut held = false;
+		$crate::expr::ImpliesExpr {
+			lhs: $crate::expr::tested($crate::assert_imp!($($lhs)*), &mut held),
+			rhs: 'rhs: {
+				if !held {
+					break 'rhs ::core::option::Option::None;
+				}
+				::core::option::Option::Some($crate::assert_imp!($($rhs)*))
+			},
+		}
+	}};
+
+	// the receiver is captured before the call, which may consume it. a temporary receiver is bound
+	// by value, next to the slot of its snapshot, which must outlive the match
+	(@call ($($call:tt)*) ($($recv:tt)*) place $method:tt ($(($($capture:tt)*) $source:tt)*)) => {
+		$crate::expr::CallExpr {
+			recv: $crate::expr::snapshot(
+				&::core::cell::OnceCell::new(),
+				$crate::SnapshotWrap($crate::CaptureWrap($crate::CaptureFallback { inner: &$($recv)* })).get(),
+			),
+			result: $($call)*,
+			args: $crate::assert_imp!(@values $(($($capture)*))*),
+		}
+	};
+	(@call $call:tt ($($recv:tt)*) temp ($($method:tt)*) ($(($($capture:tt)*) $source:tt)*)) => {
+		match (::core::cell::OnceCell::new(), $($recv)*) {
+			#[allow(unused_mut)]
+			(ref slot, mut recv) => $crate::expr::CallExpr {
+				recv: $crate::expr::snapshot(slot, $crate::SnapshotWrap($crate::CaptureWrap($crate::CaptureFallback { inner: &recv })).get()),
+				result: recv $($method)*,
+				args: $crate::assert_imp!(@values $(($($capture)*))*),
+			},
+		}
+	};
+	(@values) => { () };
+	(@values ($($head:tt)*) $($tail:tt)*) => {
+		(
+			$crate::CaptureWrap($crate::CaptureFallback { inner: $($head)* }).get(),
+			$crate::assert_imp!(@values $($tail)*),
+		)
+	};
+
+	(@power $source:tt $places:tt ($($imp:tt)*)) => {
+		$crate::power::then($places, $crate::assert_imp!($($imp)*))
+	};
+
+	(@match $source:tt ($($value:tt)*) ($($pattern:tt)*)) => {
+		match &($($value)*) {
+			__equator_value => $crate::expr::MatchExpr {
+				result: match __equator_value {
+					$($pattern)* => true,
+					_ => false,
+				},
+				value: $crate::CaptureWrap($crate::CaptureFallback { inner: __equator_value }).get(),
+			},
+		}
+	};
+	// the `else` branch of `let pattern = value`, whose bindings are not available
+	(@let_else $value:ident) => {
+		$crate::expr::MatchExpr {
+			result: false,
+			value: $crate::CaptureWrap($crate::CaptureFallback { inner: &$value }).get(),
+		}
+	};
+
+	(@unwrap $source:tt ok $value:tt) => {
+		$crate::assert_imp!(@result $value (Ok OK true) (Err ERR false))
+	};
+	(@unwrap $source:tt err $value:tt) => {
+		$crate::assert_imp!(@result $value (Err ERR true) (Ok OK false))
+	};
+	(@unwrap $source:tt some $value:tt) => {
+		$crate::assert_imp!(@option $value true)
+	};
+	(@unwrap $source:tt none $value:tt) => {
+		$crate::assert_imp!(@option $value false)
+	};
+	(@result ($($value:tt)*) $(($variant:ident $name:ident $result:expr))*) => {
+		match &($($value)*) {
+			$(::core::result::Result::$variant(__equator_value) => $crate::expr::MatchExpr {
+				result: $result,
+				value: $crate::CaptureWrap($crate::CaptureFallback {
+					inner: $crate::expr::Variant::<{ $crate::expr::$name }, _>::new(__equator_value),
+				}).get(),
+			},)*
+		}
+	};
+	(@option ($($value:tt)*) $some:expr) => {
+		match &($($value)*) {
+			::core::option::Option::Some(__equator_value) => $crate::expr::MatchExpr {
+				result: $some,
+				value: $crate::CaptureWrap($crate::CaptureFallback {
+					inner: $crate::expr::Variant::<{ $crate::expr::SOME }, _>::new(__equator_value),
+				}).get(),
+			},
+			::core::option::Option::None => $crate::expr::MatchExpr {
+				result: !$some,
+				value: $crate::CaptureWrap($crate::CaptureFallback { inner: &::core::option::Option::None::<()> }).get(),
+			},
+		}
+	};
+	// `kind(value)` as the whole condition of `assert!`, which evaluates to the payload of `value`
+	(@unwrap_or ok ($($value:tt)*) $name:ident $($panic:tt)*) => {
+		match ($($value)*) {
+			::core::result::Result::Ok($name) => $name,
+			$name => $($panic)*,
+		}
+	};
+	(@unwrap_or err ($($value:tt)*) $name:ident $($panic:tt)*) => {
+		match ($($value)*) {
+			::core::result::Result::Err($name) => $name,
+			$name => $($panic)*,
+		}
+	};
+	(@unwrap_or some ($($value:tt)*) $name:ident $($panic:tt)*) => {
+		match ($($value)*) {
+			::core::option::Option::Some($name) => $name,
+			$name => $($panic)*,
+		}
+	};
+	(@unwrap_or none ($($value:tt)*) $name:ident $($panic:tt)*) => {
+		match ($($value)*) {
+			::core::option::Option::None => (),
+			$name => $($panic)*,
+		}
+	};
+
+	// the condition is explained for each failing element while the element is still borrowed
+	(@quant $source:tt $quantifier:ident ($var:ident) ($($iter:tt)*) ($($cond:tt)*)) => {
+		$crate::quant_imp!($quantifier ($var) ($($iter)*) ($($cond)*))
+	};
+
+	// the message is only formatted if the condition fails
+	(@message ($($fmt:tt)*) ($($inner:tt)*)) => {
+		$crate::expr::MessageExpr {
+			message: $crate::expr::LazyMessage::new(&|f: &mut ::core::fmt::Formatter<'_>| f.write_fmt(::core::format_args!($($fmt)*))),
+			inner: $crate::assert_imp!($($inner)*),
+		}
+	};
+
+	// the label is only stored in the source tree
+	(@label $label:literal ($($inner:tt)*)) => {
+		$crate::expr::LabelExpr {
+			label: (),
+			inner: $crate::assert_imp!($($inner)*),
+		}
+	};
+
+	// the middle operand is evaluated once, then compared with both bounds
+	(@chain ($($lop:tt)*) ($($rop:tt)*) ($lhs:expr, $mid:expr, $rhs:expr)) => {
+		$crate::expr::ChainExpr::new(
+			&$crate::assert_imp!(@order $($lop)*),
+			&$crate::assert_imp!(@order $($rop)*),
+			$crate::Ref{inner: &$lhs}.get(),
+			$crate::Ref{inner: &$mid}.get(),
+			$crate::Ref{inner: &$rhs}.get(),
+		)
+	};
+	(@order <) => { $crate::Lt };
+	(@order <=) => { $crate::Le };
+	(@order >) => { $crate::Gt };
+	(@order >=) => { $crate::Ge };
+
+	(@list) => { () };
+	(@list ($($head:tt)*) $($tail:tt)*) => {
+		$crate::expr::AndExpr {
+			lhs: $crate::assert_imp!($($head)*),
+			rhs: $crate::assert_imp!(@list $($tail)*),
+		}
+	};
+	(at_least(($($n:tt)*) $($list:tt)*)) => { $crate::assert_imp!(@count AtLeast ($($n)*) $($list)*) };
+	(at_most(($($n:tt)*) $($list:tt)*)) => { $crate::assert_imp!(@count AtMost ($($n)*) $($list)*) };
+	(exactly(($($n:tt)*) $($list:tt)*)) => { $crate::assert_imp!(@count Exactly ($($n)*) $($list)*) };
+	(@count $kind:ident ($($n:tt)*) $($list:tt)*) => {
+		$crate::expr::CountExpr {
+			kind: $crate::expr::CountKind::$kind,
+			n: $($n)*,
+			list: $crate::assert_imp!(@list $($list)*),
+		}
+	};
+
 	(<($lhs: expr, $rhs: expr)) => {
 		$crate::expr::CmpExpr {
 			cmp: &$crate::Lt,
@@ -144,11 +568,10 @@ macro_rules! assert_imp {
 		}
 	};
 	(==($lhs: expr, $rhs: expr)) => {
-		$crate::expr::CmpExpr {
-			cmp: &$crate::Eq,
+		$crate::EqWrap($crate::EqFallback {
 			lhs: $crate::Ref{inner: &$lhs}.get(),
 			rhs: $crate::Ref{inner: &$rhs}.get(),
-		}
+		}).get()
 	};
 	(!=($lhs: expr, $rhs: expr)) => {
 		$crate::expr::CmpExpr {
@@ -157,12 +580,24 @@ macro_rules! assert_imp {
 			rhs: $crate::Ref{inner: &$rhs}.get(),
 		}
 	};
-	($test:expr,($lhs: expr, $rhs: expr)) => {$crate::expr::CmpExpr {
-			cmp: &$test,
+	(in($lhs: expr, $rhs: expr)) => {
+		$crate::expr::CmpExpr {
+			cmp: &$crate::In,
+			lhs: $crate::Ref{inner: &$lhs}.get(),
+			rhs: &$crate::Searched($crate::Ref{inner: &$rhs}.get()),
+		}
+	};
+	(~($tol:expr), ($lhs: expr, $rhs: expr)) => {$crate::expr::CmpExpr {
+			cmp: &$crate::cmp::AbsDiff::new($tol),
 			lhs: $crate::Ref{inner: &$lhs}.get(),
 			rhs: $crate::Ref{inner: &$rhs}.get(),
 		}};
 	($test:expr,~($lhs: expr, $rhs: expr)) => {$crate::expr::CmpExpr {
+			cmp: $crate::TildeWrap($crate::TildeFallback { inner: &$test }).get(),
+			lhs: $crate::Ref{inner: &$lhs}.get(),
+			rhs: $crate::Ref{inner: &$rhs}.get(),
+		}};
+	($test:expr,($lhs: expr, $rhs: expr)) => {$crate::expr::CmpExpr {
 			cmp: &$test,
 			lhs: $crate::Ref{inner: &$lhs}.get(),
 			rhs: $crate::Ref{inner: &$rhs}.get(),
@@ -170,6 +605,97 @@ macro_rules! assert_imp {
 	($cond:expr) => { $cond };
 }
 
+/// Lowers a quantifier, whose failing elements are explained into strings, so that it requires the
+/// `alloc` feature.
+#[cfg(feature = "alloc")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! quant_imp {
+	($quantifier:ident ($var:ident) ($($iter:tt)*) ($($cond:tt)*)) => {
+		&$crate::quant::$quantifier($($iter)*, |$var, explained| {
+			$crate::quant::eval(
+				$crate::assert_imp!($($cond)*),
+				const { &$crate::source_imp!($($cond)*) },
+				::core::stringify!($var),
+				$crate::CaptureWrap($crate::CaptureFallback { inner: &$var }).get(),
+				explained,
+			)
+		})
+	};
+}
+
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! quant_imp {
+	($quantifier:ident $($tt:tt)*) => {
+		::core::compile_error!(::core::concat!(
+			"`",
+			::core::stringify!($quantifier),
+			"` requires the `alloc` feature of equator"
+		))
+	};
+}
+
+/// Lowers a parsed condition to hints for the optimizer, with one hint per condition of a top-level
+/// `all(...)`, so that each of them can be used on its own.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! assume_imp {
+	(all()) => {};
+	(all(($($head:tt)*) $($tail:tt)*)) => {
+		$crate::assume_imp!($($head)*);
+		$crate::assume_imp!(all($($tail)*));
+	};
+	(@and $lhs:tt $($source:tt $rhs:tt)+) => {
+		$crate::assume_imp!(all($lhs $($rhs)+));
+	};
+	(@power ($($source:tt)*) $places:tt $imp:tt) => { $crate::assume_imp!($($source)*) };
+	(@message $fmt:tt ($($inner:tt)*)) => { $crate::assume_imp!($($inner)*) };
+	(@label $label:literal ($($inner:tt)*)) => { $crate::assume_imp!($($inner)*) };
+
+	// the condition as a plain boolean, without recording anything
+	(@bool all()) => { true };
+	(@bool all(($($head:tt)*) $($tail:tt)*)) => {
+		($crate::assume_imp!(@bool $($head)*) && $crate::assume_imp!(@bool all($($tail)*)))
+	};
+	(@bool any()) => { false };
+	(@bool any(($($head:tt)*) $($tail:tt)*)) => {
+		($crate::assume_imp!(@bool $($head)*) || $crate::assume_imp!(@bool any($($tail)*)))
+	};
+	(@bool @and ($($lhs:tt)*) $($source:tt ($($rhs:tt)*))+) => {
+		($crate::assume_imp!(@bool $($lhs)*) $(&& $crate::assume_imp!(@bool $($rhs)*))+)
+	};
+	(@bool @or ($($lhs:tt)*) $($source:tt ($($rhs:tt)*))+) => {
+		($crate::assume_imp!(@bool $($lhs)*) $(|| $crate::assume_imp!(@bool $($rhs)*))+)
+	};
+	(@bool not(($($inner:tt)*))) => { !$crate::assume_imp!(@bool $($inner)*) };
+	(@bool implies(($($lhs:tt)*) ($($rhs:tt)*))) => {
+		(!$crate::assume_imp!(@bool $($lhs)*) || $crate::assume_imp!(@bool $($rhs)*))
+	};
+	(@bool at_least(($($n:tt)*) $(($($list:tt)*))*)) => { ($crate::assume_imp!(@count $(($($list)*))*) >= ($($n)*)) };
+	(@bool at_most(($($n:tt)*) $(($($list:tt)*))*)) => { ($crate::assume_imp!(@count $(($($list)*))*) <= ($($n)*)) };
+	(@bool exactly(($($n:tt)*) $(($($list:tt)*))*)) => { ($crate::assume_imp!(@count $(($($list)*))*) == ($($n)*)) };
+	(@count $(($($list:tt)*))*) => { (0usize $(+ ($crate::assume_imp!(@bool $($list)*) as usize))*) };
+	(@bool @quant $source:tt for_all ($var:ident) ($($iter:tt)*) ($($cond:tt)*)) => {
+		::core::iter::Iterator::all(&mut ::core::iter::IntoIterator::into_iter($($iter)*), |$var| {
+			$crate::assume_imp!(@bool $($cond)*)
+		})
+	};
+	(@bool @quant $source:tt exists ($var:ident) ($($iter:tt)*) ($($cond:tt)*)) => {
+		::core::iter::Iterator::any(&mut ::core::iter::IntoIterator::into_iter($($iter)*), |$var| {
+			$crate::assume_imp!(@bool $($cond)*)
+		})
+	};
+	(@bool @lazy $source:tt ($($cond:tt)*)) => { $crate::assume_imp!(@bool $($cond)*) };
+	(@bool @power ($($source:tt)*) $places:tt $imp:tt) => { $crate::assume_imp!(@bool $($source)*) };
+	(@bool @message $fmt:tt ($($inner:tt)*)) => { $crate::assume_imp!(@bool $($inner)*) };
+	(@bool @label $label:literal ($($inner:tt)*)) => { $crate::assume_imp!(@bool $($inner)*) };
+	(@bool $($cond:tt)*) => { $crate::Test::test(&$crate::assert_imp!($($cond)*)) };
+
+	($($cond:tt)*) => { ::core::hint::assert_unchecked($crate::assume_imp!(@bool $($cond)*)) };
+}
+
 #[macro_export]
 macro_rules! assert {
     ($($tokens: tt)*) => {
@@ -177,11 +703,32 @@ macro_rules! assert {
     };
 }
 
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! check {
+    ($($tokens: tt)*) => {
+        $crate::imp::check!(($crate) $($tokens)*)
+    };
+}
+
+/// Asserts the condition in debug builds, and lets the optimizer assume that it holds in release
+/// builds, using [`core::hint::assert_unchecked`].
+///
+/// # Safety
+/// The condition must hold, since it is not tested in release builds. The expansion calls an unsafe
+/// function, so it must be wrapped in an `unsafe` block.
+#[macro_export]
+macro_rules! assume {
+    ($($tokens: tt)*) => {
+        $crate::imp::assume!(($crate) $($tokens)*)
+    };
+}
+
 #[macro_export]
 macro_rules! debug_assert {
     ($($tokens: tt)*) => {
         if cfg!(debug_assertions) {
-	        $crate::imp::assert!(($crate) $($tokens)*)
+	        $crate::imp::assert!(($crate) $($tokens)*);
         }
     };
 }
@@ -212,6 +759,12 @@ pub mod expr {
 		pub rhs: core::mem::MaybeUninit<*const ()>,
 	}
 
+	#[derive(Copy, Clone)]
+	pub struct CmpFns<Test, Explain> {
+		pub test: Test,
+		pub explain: Explain,
+	}
+
 	#[derive(Copy, Clone)]
 	#[repr(C)]
 	pub struct CustomCmp {
@@ -229,73 +782,289 @@ pub mod expr {
 	}
 
 	#[derive(Copy, Clone, Debug)]
-	pub struct AndExpr<Lhs, Rhs> {
+	pub struct SliceEqExpr<Lhs, Rhs> {
 		pub lhs: Lhs,
 		pub rhs: Rhs,
 	}
 
+	#[derive(Copy, Clone)]
+	pub struct SliceEq {
+		pub lhs: (*const (), usize),
+		pub rhs: (*const (), usize),
+	}
+
 	#[derive(Copy, Clone, Debug)]
-	pub struct OrExpr<Lhs, Rhs> {
+	pub struct CallExpr<Result, Recv, Args> {
+		pub result: Result,
+		pub recv: Recv,
+		pub args: Args,
+	}
+
+	/// `value matches pattern`, or the `let pattern = value` condition of an assertion.
+	#[derive(Copy, Clone, Debug)]
+	pub struct MatchExpr<Result, Value> {
+		pub result: Result,
+		pub value: Value,
+	}
+
+	/// `lo <= x < hi`, made of the comparisons `lo <= x` and `x < hi`.
+	#[derive(Copy, Clone, Debug)]
+	pub struct ChainExpr<Lhs, Rhs> {
 		pub lhs: Lhs,
 		pub rhs: Rhs,
 	}
-}
 
-pub trait Cmp<Lhs: ?Sized, Rhs: ?Sized> {
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool;
-}
+	impl<'a, LCmp, RCmp, Lhs, Mid, Rhs> ChainExpr<CmpExpr<&'a LCmp, &'a Lhs, &'a Mid>, CmpExpr<&'a RCmp, &'a Mid, &'a Rhs>> {
+		#[inline(always)]
+		pub const fn new(lcmp: &'a LCmp, rcmp: &'a RCmp, lhs: &'a Lhs, mid: &'a Mid, rhs: &'a Rhs) -> Self {
+			Self {
+				lhs: CmpExpr { cmp: lcmp, lhs, rhs: mid },
+				rhs: CmpExpr { cmp: rcmp, lhs: mid, rhs },
+			}
+		}
+	}
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Eq;
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Ne;
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Le;
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Ge;
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Lt;
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Gt;
+	pub const OK: u8 = 0;
+	pub const ERR: u8 = 1;
+	pub const SOME: u8 = 2;
 
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialEq<Rhs>> Cmp<Lhs, Rhs> for Eq {
-	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs == *rhs
-	}
-}
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialEq<Rhs>> Cmp<Lhs, Rhs> for Ne {
-	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs != *rhs
+	/// Payload of an `Ok`, `Err` or `Some` variant, which is printed along with the name of the
+	/// variant.
+	#[repr(transparent)]
+	pub struct Variant<const V: u8, T: ?Sized>(pub T);
+
+	impl<const V: u8, T: ?Sized> Variant<V, T> {
+		#[inline(always)]
+		pub const fn new(payload: &T) -> &Self {
+			// SAFETY: `Variant` is a transparent wrapper
+			unsafe { &*(payload as *const T as *const Self) }
+		}
 	}
-}
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Le {
-	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs <= *rhs
+
+	impl<const V: u8, T: ?Sized + core::fmt::Debug> core::fmt::Debug for Variant<V, T> {
+		fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+			let name = match V {
+				OK => "Ok",
+				ERR => "Err",
+				_ => "Some",
+			};
+			f.debug_tuple(name).field(&&self.0).finish()
+		}
 	}
-}
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Ge {
+
+	/// Copy of a value whose type implements `Copy` and `Debug`, see [`SnapshotWrap`](crate::SnapshotWrap).
+	#[derive(Copy, Clone)]
+	pub struct Copied<T>(pub T);
+
+	/// Stores `snapshot` into `slot`, which outlives the assertion, and returns its captured value.
 	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs >= *rhs
+	pub fn snapshot<S: crate::Snapshot>(slot: &core::cell::OnceCell<S::Stored>, snapshot: S) -> Value {
+		S::value(slot.get_or_init(|| snapshot.store()))
 	}
-}
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Lt {
-	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs < *rhs
+
+	/// Captured value, which is only printed if its type implements `Debug`.
+	#[derive(Copy, Clone)]
+	pub struct Value {
+		pub ptr: *const (),
+		pub fmt: Option<fn(*const (), &mut core::fmt::Formatter<'_>) -> core::fmt::Result>,
 	}
-}
-impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Gt {
-	#[inline(always)]
-	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
-		*lhs > *rhs
+
+	#[derive(Copy, Clone, Debug)]
+	pub struct AndExpr<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
 	}
-}
 
-#[doc(hidden)]
+	#[derive(Copy, Clone, Debug)]
+	pub struct OrExpr<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
+	}
+
+	/// `lhs && rhs`, whose right operand is `None` if it was not evaluated because `lhs` did not
+	/// hold. `a && b && c` is stored as `a && (b && c)`.
+	#[derive(Copy, Clone, Debug)]
+	pub struct LazyAndExpr<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
+	}
+
+	/// `lhs || rhs`, whose right operand is `None` if it was not evaluated because `lhs` held.
+	/// `a || b || c` is stored as `a || (b || c)`.
+	#[derive(Copy, Clone, Debug)]
+	pub struct LazyOrExpr<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
+	}
+
+	/// Source of a condition that is only evaluated if needed, whose `source` text is reported
+	/// when it is not.
+	#[derive(Copy, Clone, Debug)]
+	pub struct LazySource<Inner> {
+		pub source: &'static str,
+		pub inner: Inner,
+	}
+
+	/// Tests `cond` and stores whether it held in `held`, which decides whether the conditions after
+	/// it are evaluated.
+	#[inline(always)]
+	pub fn tested<T: crate::Test>(cond: T, held: &mut bool) -> T {
+		*held = cond.test();
+		cond
+	}
+
+	/// Source of an [`AndExpr`], [`OrExpr`], [`LazyAndExpr`] or [`LazyOrExpr`], whose `rhs` is the
+	/// rest of the list if `tail` is set, rather than a single condition written by the user.
+	#[derive(Copy, Clone, Debug)]
+	pub struct ListSource<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
+		pub tail: bool,
+	}
+
+	#[derive(Copy, Clone, Debug)]
+	pub struct NotExpr<Inner> {
+		pub inner: Inner,
+	}
+
+	#[derive(Copy, Clone, Debug)]
+	pub struct ImpliesExpr<Lhs, Rhs> {
+		pub lhs: Lhs,
+		pub rhs: Rhs,
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+	pub enum CountKind {
+		AtLeast,
+		AtMost,
+		Exactly,
+	}
+
+	#[derive(Copy, Clone, Debug)]
+	pub struct CountExpr<Kind, N, List> {
+		pub kind: Kind,
+		pub n: N,
+		pub list: List,
+	}
+
+	/// `(cond, "fmt", args...)`, whose message is printed above the explanation of `cond` when it
+	/// fails.
+	#[derive(Copy, Clone, Debug)]
+	pub struct MessageExpr<Message, Inner> {
+		pub message: Message,
+		pub inner: Inner,
+	}
+
+	/// Message of a [`MessageExpr`], which is formatted by a closure borrowing its arguments.
+	#[derive(Copy, Clone)]
+	pub struct LazyMessage {
+		pub ptr: *const (),
+		pub fmt: fn(*const (), &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+	}
+
+	impl LazyMessage {
+		#[inline(always)]
+		pub fn new<F: Fn(&mut core::fmt::Formatter<'_>) -> core::fmt::Result>(message: &F) -> Self {
+			Self {
+				ptr: message as *const F as *const (),
+				// SAFETY: the message outlives the statement of the assertion, during which it is formatted
+				fmt: |ptr, f| unsafe { (*(ptr as *const F))(f) },
+			}
+		}
+	}
+
+	impl core::fmt::Display for LazyMessage {
+		fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+			(self.fmt)(self.ptr, f)
+		}
+	}
+
+	/// `"label" => cond`, whose label is printed as a heading above the explanation of `cond`.
+	#[derive(Copy, Clone, Debug)]
+	pub struct LabelExpr<Label, Inner> {
+		pub label: Label,
+		pub inner: Inner,
+	}
+}
+
+pub trait Cmp<Lhs: ?Sized, Rhs: ?Sized> {
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool;
+
+	/// Explains why the comparison failed, printed under the values of `lhs` and `rhs`.
+	///
+	/// Writing nothing, as the default implementation does, omits the explanation.
+	#[inline(always)]
+	fn explain(&self, lhs: &Lhs, rhs: &Rhs, f: &mut fmt::Formatter) -> fmt::Result {
+		let _ = (lhs, rhs, f);
+		Ok(())
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Eq;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ne;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Le;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Ge;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Lt;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Gt;
+/// Comparator of `item in collection`, which holds if `collection` is a range containing `item`, or
+/// a collection containing an element equal to `item`. See [`contains::Contains`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct In;
+
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialEq<Rhs>> Cmp<Lhs, Rhs> for Eq {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs == *rhs
+	}
+}
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialEq<Rhs>> Cmp<Lhs, Rhs> for Ne {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs != *rhs
+	}
+}
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Le {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs <= *rhs
+	}
+}
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Ge {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs >= *rhs
+	}
+}
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Lt {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs < *rhs
+	}
+}
+impl<Rhs: ?Sized, Lhs: ?Sized + PartialOrd<Rhs>> Cmp<Lhs, Rhs> for Gt {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Rhs) -> bool {
+		*lhs > *rhs
+	}
+}
+impl<Rhs: ?Sized + contains::Contains<Lhs>, Lhs: ?Sized> Cmp<Lhs, Searched<'_, Rhs>> for In {
+	#[inline(always)]
+	fn test(&self, lhs: &Lhs, rhs: &Searched<'_, Rhs>) -> bool {
+		rhs.0.contains(lhs)
+	}
+
+	fn explain(&self, lhs: &Lhs, rhs: &Searched<'_, Rhs>, f: &mut fmt::Formatter) -> fmt::Result {
+		rhs.0.explain(lhs, f)
+	}
+}
+
+#[doc(hidden)]
 pub struct CmpExpr;
 #[doc(hidden)]
 pub struct CustomCmpExpr<E>(pub core::marker::PhantomData<E>);
@@ -323,8 +1092,6 @@ impl<'a> From<Message<'a>> for core::fmt::Arguments<'a> {
 
 #[cfg(test)]
 mod tests {
-	use super::*;
-
 	#[test]
 	#[should_panic]
 	fn test_assert() {
@@ -348,8 +1115,21 @@ pub trait Panic: Copy {
 	type Result: Copy;
 
 	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp);
+	/// # Safety
+	/// the parts must come from a call to [`Panic::into_parts`] on a value of the same type.
 	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self;
 	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result;
+	/// Explains why the condition held, introducing each leaf with `header`.
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result;
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report;
 	fn test(&self, vtable: &'static Self::VTable) -> Self::Result;
 	fn reduce(result: Self::Result) -> bool;
 }
@@ -399,8 +1179,29 @@ impl Panic for bool {
 	}
 
 	fn explain(self, source: &Self::Source, result: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "Assertion failed: {source}\n")?;
-		write!(f, "- {source} = {result:#?}")
+		f.write_str("Assertion failed: ")?;
+		Style::BOLD.write(f, source)?;
+		write!(f, "\n- {source} = ")?;
+		Style::RED.paint(f, |f| write!(f, "{result:#?}"))
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		_: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		write!(f, "{header}: ")?;
+		Style::BOLD.write(f, source)?;
+		write!(f, "\n- {source} = ")?;
+		Style::RED.paint(f, |f| write!(f, "{result:#?}"))
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, _: &'static Self::VTable) -> Report {
+		Report::leaf(ReportKind::Bool, (*source).into(), result)
 	}
 
 	fn test(&self, _: &'static Self::VTable) -> Self::Result {
@@ -435,7 +1236,10 @@ impl Panic for expr::Cmp {
 	type Rhs = core::mem::MaybeUninit<*const ()>;
 	type Source = expr::CmpExpr<&'static str, &'static str, &'static str>;
 	type VTable = expr::CmpExpr<
-		fn(*const (), core::mem::MaybeUninit<*const ()>, core::mem::MaybeUninit<*const ()>) -> bool,
+		expr::CmpFns<
+			fn(*const (), core::mem::MaybeUninit<*const ()>, core::mem::MaybeUninit<*const ()>) -> bool,
+			fn(*const (), core::mem::MaybeUninit<*const ()>, core::mem::MaybeUninit<*const ()>, &mut fmt::Formatter<'_>) -> fmt::Result,
+		>,
 		fn(core::mem::MaybeUninit<*const ()>, f: &mut fmt::Formatter<'_>) -> fmt::Result,
 		fn(core::mem::MaybeUninit<*const ()>, f: &mut fmt::Formatter<'_>) -> fmt::Result,
 	>;
@@ -452,15 +1256,56 @@ impl Panic for expr::Cmp {
 
 	fn explain(self, source: &Self::Source, _: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
 		let expr::CmpExpr { lhs, rhs, cmp } = *source;
-		write!(f, "Assertion failed: {lhs} {cmp} {rhs}\n")?;
-		write!(f, "- {lhs} = ")?;
-		(vtable.lhs)(self.lhs, f)?;
-		write!(f, "\n- {rhs} = ")?;
-		(vtable.rhs)(self.rhs, f)
+		f.write_str("Assertion failed: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{lhs} {cmp} {rhs}"))?;
+
+		#[cfg(feature = "diff")]
+		if cmp == "==" {
+			let lhs_value = alloc::format!("{:#?}", ErasedValue(vtable.lhs, self.lhs));
+			let rhs_value = alloc::format!("{:#?}", ErasedValue(vtable.rhs, self.rhs));
+			if lhs_value.contains('\n') || rhs_value.contains('\n') {
+				f.write_str("\n")?;
+				return diff::write_diff(f, lhs, rhs, &lhs_value, &rhs_value);
+			}
+		}
+
+		self.explain_values(source, vtable, f)?;
+		self.explain_cmp(vtable, f)
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		_: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		let expr::CmpExpr { lhs, rhs, cmp } = *source;
+		write!(f, "{header}: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{lhs} {cmp} {rhs}"))?;
+		self.explain_values(source, vtable, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let expr::CmpExpr { lhs, rhs, cmp } = *source;
+		Report {
+			op: Some(cmp),
+			lhs: Some(Operand {
+				source: lhs,
+				value: alloc::format!("{:#?}", ErasedValue(vtable.lhs, self.lhs)),
+			}),
+			rhs: Some(Operand {
+				source: rhs,
+				value: alloc::format!("{:#?}", ErasedValue(vtable.rhs, self.rhs)),
+			}),
+			..Report::leaf(ReportKind::Cmp, alloc::format!("{lhs} {cmp} {rhs}"), result)
+		}
 	}
 
 	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
-		(vtable.cmp)(self.cmp, self.lhs, self.rhs)
+		(vtable.cmp.test)(self.cmp, self.lhs, self.rhs)
 	}
 
 	fn reduce(result: Self::Result) -> bool {
@@ -468,6 +1313,59 @@ impl Panic for expr::Cmp {
 	}
 }
 
+impl expr::Cmp {
+	fn explain_values(
+		self,
+		source: &<Self as Panic>::Source,
+		vtable: &'static <Self as Panic>::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		let expr::CmpExpr { lhs, rhs, .. } = *source;
+		write!(f, "\n- {lhs} = ")?;
+		Style::RED.paint(f, |f| (vtable.lhs)(self.lhs, f))?;
+		write!(f, "\n- {rhs} = ")?;
+		Style::GREEN.paint(f, |f| (vtable.rhs)(self.rhs, f))
+	}
+
+	/// Writes the explanation provided by [`Cmp::explain`], as an indented list item.
+	fn explain_cmp(self, vtable: &'static <Self as Panic>::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		struct Explain(expr::Cmp, &'static <expr::Cmp as Panic>::VTable);
+		impl fmt::Display for Explain {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				let Self(this, vtable) = *self;
+				(vtable.cmp.explain)(this.cmp, this.lhs, this.rhs, f)
+			}
+		}
+
+		fmt::write(&mut ListItem { f, started: false }, format_args!("{}", Explain(self, vtable)))
+	}
+}
+
+/// Writes to `f` as a list item, which is only started once something is written.
+struct ListItem<'a, 'b> {
+	f: &'a mut fmt::Formatter<'b>,
+	started: bool,
+}
+
+impl fmt::Write for ListItem<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if s.is_empty() {
+			return Ok(());
+		}
+		if !self.started {
+			self.started = true;
+			self.f.write_str("\n- ")?;
+		}
+		for (i, line) in s.split('\n').enumerate() {
+			if i > 0 {
+				self.f.write_str("\n  ")?;
+			}
+			self.f.write_str(line)?;
+		}
+		Ok(())
+	}
+}
+
 impl<C: Cmp<L, R>, L: fmt::Debug, R: fmt::Debug> Test for expr::CmpExpr<&C, &L, &R> {
 	type Panic = expr::Cmp;
 
@@ -480,10 +1378,21 @@ impl<C: Cmp<L, R>, L: fmt::Debug, R: fmt::Debug> Test for expr::CmpExpr<&C, &L,
 					&& !core::mem::needs_drop::<R>()
 			} {
 				expr::CmpExpr {
-					cmp: core::mem::transmute(
-						(|c, l, r| C::test(c, &*(&raw const l as *const L), &*(&raw const r as *const R)))
-							as fn(&C, core::mem::MaybeUninit<*const ()>, core::mem::MaybeUninit<*const ()>) -> bool,
-					),
+					cmp: expr::CmpFns {
+						test: core::mem::transmute(
+							(|c, l, r| C::test(c, &*(&raw const l as *const L), &*(&raw const r as *const R)))
+								as fn(&C, core::mem::MaybeUninit<*const ()>, core::mem::MaybeUninit<*const ()>) -> bool,
+						),
+						explain: core::mem::transmute(
+							(|c, l, r, f| C::explain(c, &*(&raw const l as *const L), &*(&raw const r as *const R), f))
+								as fn(
+									&C,
+									core::mem::MaybeUninit<*const ()>,
+									core::mem::MaybeUninit<*const ()>,
+									&mut fmt::Formatter<'_>,
+								) -> fmt::Result,
+						),
+					},
 					lhs: core::mem::transmute(
 						(|x, f| L::fmt(&*(&raw const x as *const L), f))
 							as fn(core::mem::MaybeUninit<*const ()>, &mut fmt::Formatter<'_>) -> fmt::Result,
@@ -495,7 +1404,10 @@ impl<C: Cmp<L, R>, L: fmt::Debug, R: fmt::Debug> Test for expr::CmpExpr<&C, &L,
 				}
 			} else {
 				expr::CmpExpr {
-					cmp: core::mem::transmute(C::test as fn(&C, &L, &R) -> bool),
+					cmp: expr::CmpFns {
+						test: core::mem::transmute(C::test as fn(&C, &L, &R) -> bool),
+						explain: core::mem::transmute(C::explain as fn(&C, &L, &R, &mut fmt::Formatter<'_>) -> fmt::Result),
+					},
 					lhs: core::mem::transmute(L::fmt as fn(&L, &mut fmt::Formatter<'_>) -> fmt::Result),
 					rhs: core::mem::transmute(R::fmt as fn(&R, &mut fmt::Formatter<'_>) -> fmt::Result),
 				}
@@ -505,7 +1417,7 @@ impl<C: Cmp<L, R>, L: fmt::Debug, R: fmt::Debug> Test for expr::CmpExpr<&C, &L,
 
 	#[inline(always)]
 	fn test(&self) -> bool {
-		self.cmp.test(&self.lhs, &self.rhs)
+		self.cmp.test(self.lhs, self.rhs)
 	}
 
 	#[inline(always)]
@@ -536,157 +1448,1619 @@ impl<C: Cmp<L, R>, L: fmt::Debug, R: fmt::Debug> Test for expr::CmpExpr<&C, &L,
 	}
 }
 
-impl<L: Panic, R: Panic> Panic for expr::AndExpr<L, R> {
-	type Cmp = expr::AndExpr<L::Cmp, R::Cmp>;
-	type Lhs = expr::AndExpr<L::Lhs, R::Lhs>;
-	type Result = expr::AndExpr<L::Result, R::Result>;
-	type Rhs = expr::AndExpr<L::Rhs, R::Rhs>;
-	type Source = expr::AndExpr<&'static L::Source, &'static R::Source>;
-	type VTable = expr::AndExpr<&'static L::VTable, &'static R::VTable>;
+/// Maximum number of mismatching elements listed when a sequence comparison fails.
+const MAX_MISMATCHES: usize = 8;
+
+/// Type-erased sequence, formatted element by element through its vtable entry.
+#[cfg(feature = "alloc")]
+struct ErasedSlice((*const (), usize), fn(*const (), usize, &mut fmt::Formatter<'_>) -> fmt::Result);
+
+#[cfg(feature = "alloc")]
+struct ErasedElem(*const (), usize, fn(*const (), usize, &mut fmt::Formatter<'_>) -> fmt::Result);
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ErasedElem {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		(self.2)(self.0, self.1, f)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ErasedSlice {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let ((ptr, len), fmt) = (self.0, self.1);
+		f.debug_list().entries((0..len).map(|i| ErasedElem(ptr, i, fmt))).finish()
+	}
+}
+
+impl Panic for expr::SliceEq {
+	type Cmp = ();
+	type Lhs = (*const (), usize);
+	type Result = bool;
+	type Rhs = (*const (), usize);
+	type Source = expr::CmpExpr<&'static str, &'static str, &'static str>;
+	type VTable = expr::CmpExpr<
+		fn(*const (), *const (), usize) -> bool,
+		fn(*const (), usize, &mut fmt::Formatter<'_>) -> fmt::Result,
+		fn(*const (), usize, &mut fmt::Formatter<'_>) -> fmt::Result,
+	>;
 
 	#[inline(always)]
 	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
-		let lhs = self.lhs.into_parts();
-		let rhs = self.rhs.into_parts();
-
-		(
-			expr::AndExpr { lhs: lhs.0, rhs: rhs.0 },
-			expr::AndExpr { lhs: lhs.1, rhs: rhs.1 },
-			expr::AndExpr { lhs: lhs.2, rhs: rhs.2 },
-		)
+		(self.lhs, self.rhs, ())
 	}
 
 	#[inline(always)]
-	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
-		Self {
-			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
-			rhs: R::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
-		}
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, _: Self::Cmp) -> Self {
+		Self { lhs, rhs }
 	}
 
-	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
-		let lhs_eval = L::reduce(result.lhs);
-		let rhs_eval = R::reduce(result.rhs);
-		if !(lhs_eval && rhs_eval) {
-			if !lhs_eval {
-				self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
-				if !rhs_eval {
-					f.write_str("\n")?;
+	fn explain(self, source: &Self::Source, _: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let expr::CmpExpr { lhs, rhs, cmp } = *source;
+		f.write_str("Assertion failed: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{lhs} {cmp} {rhs}"))?;
+
+		self.explain_lens(source, f)?;
+
+		let (l, r) = (self.lhs.0, self.rhs.0);
+		let mut mismatches = (0..Ord::min(self.lhs.1, self.rhs.1)).filter(|&i| !(vtable.cmp)(l, r, i));
+		if let Some(first) = mismatches.next() {
+			write!(f, "\n- first mismatch at index {first}")?;
+
+			let mut count = 0;
+			for i in core::iter::once(first).chain(mismatches) {
+				if count < MAX_MISMATCHES {
+					write!(f, "\n  - [{i}]: ")?;
+					Style::RED.paint(f, |f| (vtable.lhs)(l, i, f))?;
+					f.write_str(" != ")?;
+					Style::GREEN.paint(f, |f| (vtable.rhs)(r, i, f))?;
 				}
+				count += 1;
 			}
-			if !rhs_eval {
-				self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+			if count > MAX_MISMATCHES {
+				write!(f, "\n  - ... and {} more", count - MAX_MISMATCHES)?;
+			}
+		}
+
+		// the diff also shows the elements around the mismatches, as well as insertions and removals
+		#[cfg(feature = "diff")]
+		{
+			let lhs_value = alloc::format!("{:#?}", ErasedSlice(self.lhs, vtable.lhs));
+			let rhs_value = alloc::format!("{:#?}", ErasedSlice(self.rhs, vtable.rhs));
+			if lhs_value.contains('\n') || rhs_value.contains('\n') {
+				f.write_str("\n")?;
+				diff::write_diff(f, lhs, rhs, &lhs_value, &rhs_value)?;
 			}
 		}
 		Ok(())
 	}
 
-	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
-		Self::Result {
-			lhs: self.lhs.test(vtable.lhs),
-			rhs: self.rhs.test(vtable.rhs),
+	fn explain_held(self, header: &str, source: &Self::Source, _: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let expr::CmpExpr { lhs, rhs, cmp } = *source;
+		write!(f, "{header}: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{lhs} {cmp} {rhs}"))?;
+		self.explain_lens(source, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let expr::CmpExpr { lhs, rhs, cmp } = *source;
+		Report {
+			op: Some(cmp),
+			lhs: Some(Operand {
+				source: lhs,
+				value: alloc::format!("{:#?}", ErasedSlice(self.lhs, vtable.lhs)),
+			}),
+			rhs: Some(Operand {
+				source: rhs,
+				value: alloc::format!("{:#?}", ErasedSlice(self.rhs, vtable.rhs)),
+			}),
+			..Report::leaf(ReportKind::Cmp, alloc::format!("{lhs} {cmp} {rhs}"), result)
 		}
 	}
 
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		self.lhs.1 == self.rhs.1 && (0..self.lhs.1).all(|i| (vtable.cmp)(self.lhs.0, self.rhs.0, i))
+	}
+
 	fn reduce(result: Self::Result) -> bool {
-		L::reduce(result.lhs) && R::reduce(result.rhs)
+		result
 	}
 }
 
-impl<L: Test, R: Test> Test for expr::AndExpr<L, R> {
-	type Panic = expr::AndExpr<L::Panic, R::Panic>;
+impl expr::SliceEq {
+	fn explain_lens(self, source: &<Self as Panic>::Source, f: &mut fmt::Formatter) -> fmt::Result {
+		let expr::CmpExpr { lhs, rhs, .. } = *source;
+		write!(f, "\n- {lhs}.len() = ")?;
+		Style::RED.paint(f, |f| write!(f, "{}", self.lhs.1))?;
+		write!(f, "\n- {rhs}.len() = ")?;
+		Style::GREEN.paint(f, |f| write!(f, "{}", self.rhs.1))
+	}
+}
 
-	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
-		expr::AndExpr {
-			lhs: L::VTABLE,
-			rhs: R::VTABLE,
-		}
+impl<L: SliceLike + PartialEq<R>, R: SliceLike> Test for expr::SliceEqExpr<&L, &R>
+where
+	L::Item: PartialEq<R::Item> + fmt::Debug,
+	R::Item: fmt::Debug,
+{
+	type Panic = expr::SliceEq;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &expr::CmpExpr {
+		cmp: |l, r, i| unsafe { *(l as *const L::Item).add(i) == *(r as *const R::Item).add(i) },
+		lhs: |l, i, f| unsafe { fmt::Debug::fmt(&*(l as *const L::Item).add(i), f) },
+		rhs: |r, i, f| unsafe { fmt::Debug::fmt(&*(r as *const R::Item).add(i), f) },
 	};
 
 	#[inline(always)]
 	fn test(&self) -> bool {
-		self.lhs.test() & self.rhs.test()
+		*self.lhs == *self.rhs
 	}
 
 	#[inline(always)]
 	fn as_dyn(self) -> Self::Panic {
-		Self::Panic {
-			lhs: self.lhs.as_dyn(),
-			rhs: self.rhs.as_dyn(),
+		let (lhs, rhs) = (self.lhs.as_slice(), self.rhs.as_slice());
+		expr::SliceEq {
+			lhs: (lhs.as_ptr() as *const (), lhs.len()),
+			rhs: (rhs.as_ptr() as *const (), rhs.len()),
 		}
 	}
 }
 
-impl<L: Panic, R: Panic> Panic for expr::OrExpr<L, R> {
-	type Cmp = expr::OrExpr<L::Cmp, R::Cmp>;
-	type Lhs = expr::OrExpr<L::Lhs, R::Lhs>;
-	type Result = expr::OrExpr<L::Result, R::Result>;
-	type Rhs = expr::OrExpr<L::Rhs, R::Rhs>;
-	type Source = expr::OrExpr<&'static L::Source, &'static R::Source>;
-	type VTable = expr::OrExpr<&'static L::VTable, &'static R::VTable>;
+impl expr::Value {
+	fn explain(self, source: &str, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.fmt {
+			Some(fmt) => {
+				write!(f, "\n- {source} = ")?;
+				Style::RED.paint(f, |f| fmt(self.ptr, f))
+			}
+			None => Ok(()),
+		}
+	}
 
-	#[inline(always)]
-	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
-		let lhs = self.lhs.into_parts();
-		let rhs = self.rhs.into_parts();
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &'static str) -> Option<Operand> {
+		struct Erased(expr::Value, fn(*const (), &mut fmt::Formatter<'_>) -> fmt::Result);
+		impl fmt::Debug for Erased {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				(self.1)(self.0.ptr, f)
+			}
+		}
 
-		(
-			expr::OrExpr { lhs: lhs.0, rhs: rhs.0 },
-			expr::OrExpr { lhs: lhs.1, rhs: rhs.1 },
-			expr::OrExpr { lhs: lhs.2, rhs: rhs.2 },
-		)
+		self.fmt.map(|fmt| Operand {
+			source,
+			value: alloc::format!("{:#?}", Erased(self, fmt)),
+		})
 	}
+}
 
-	#[inline(always)]
-	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+/// Values captured from the arguments of a method call, stored as `(head, tail)` and terminated by
+/// `()`.
+#[doc(hidden)]
+pub trait ValueList: Copy {
+	fn explain_each(self, sources: &[&str], f: &mut fmt::Formatter) -> fmt::Result;
+	#[cfg(feature = "alloc")]
+	fn report_each(self, sources: &[&'static str], operands: &mut alloc::vec::Vec<Operand>);
+}
+
+impl ValueList for () {
+	fn explain_each(self, _: &[&str], _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report_each(self, _: &[&'static str], _: &mut alloc::vec::Vec<Operand>) {}
+}
+
+impl<Tail: ValueList> ValueList for (expr::Value, Tail) {
+	fn explain_each(self, sources: &[&str], f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.explain(sources[0], f)?;
+		self.1.explain_each(&sources[1..], f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report_each(self, sources: &[&'static str], operands: &mut alloc::vec::Vec<Operand>) {
+		operands.extend(self.0.report(sources[0]));
+		self.1.report_each(&sources[1..], operands)
+	}
+}
+
+impl<Args: ValueList> Panic for expr::CallExpr<bool, expr::Value, Args> {
+	type Cmp = Args;
+	type Lhs = bool;
+	type Result = bool;
+	type Rhs = expr::Value;
+	type Source = expr::CallExpr<&'static str, &'static str, &'static [&'static str]>;
+	type VTable = ();
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		(self.result, self.recv, self.args)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			result: lhs,
+			recv: rhs,
+			args: cmp,
+		}
+	}
+
+	fn explain(self, source: &Self::Source, _: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		self.explain_held("Assertion failed", source, self.result, vtable, f)
+	}
+
+	fn explain_held(self, header: &str, source: &Self::Source, _: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{header}: ")?;
+		Style::BOLD.write(f, source.result)?;
+		self.recv.explain(source.recv, f)?;
+		self.args.explain_each(source.args, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, _: &'static Self::VTable) -> Report {
+		let mut args = alloc::vec::Vec::new();
+		self.args.report_each(source.args, &mut args);
+		Report {
+			lhs: self.recv.report(source.recv),
+			args,
+			..Report::leaf(ReportKind::Call, source.result.into(), result)
+		}
+	}
+
+	fn test(&self, _: &'static Self::VTable) -> Self::Result {
+		self.result
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		result
+	}
+}
+
+impl<Args: ValueList> Test for expr::CallExpr<bool, expr::Value, Args> {
+	type Panic = Self;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &();
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.result
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		self
+	}
+}
+
+impl Panic for expr::MatchExpr<bool, expr::Value> {
+	type Cmp = ();
+	type Lhs = bool;
+	type Result = bool;
+	type Rhs = expr::Value;
+	type Source = expr::MatchExpr<&'static str, &'static str>;
+	type VTable = ();
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		(self.result, self.value, ())
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, _: Self::Cmp) -> Self {
+		Self { result: lhs, value: rhs }
+	}
+
+	fn explain(self, source: &Self::Source, _: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		self.explain_held("Assertion failed", source, self.result, vtable, f)
+	}
+
+	fn explain_held(self, header: &str, source: &Self::Source, _: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{header}: ")?;
+		Style::BOLD.write(f, source.result)?;
+		self.value.explain(source.value, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, _: &'static Self::VTable) -> Report {
+		Report {
+			lhs: self.value.report(source.value),
+			..Report::leaf(ReportKind::Match, source.result.into(), result)
+		}
+	}
+
+	fn test(&self, _: &'static Self::VTable) -> Self::Result {
+		self.result
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		result
+	}
+}
+
+impl Test for expr::MatchExpr<bool, expr::Value> {
+	type Panic = Self;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &();
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.result
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		self
+	}
+}
+
+impl Panic for expr::ChainExpr<expr::Cmp, expr::Cmp> {
+	type Cmp = ();
+	type Lhs = expr::Cmp;
+	type Result = expr::ChainExpr<bool, bool>;
+	type Rhs = expr::Cmp;
+	type Source = expr::ChainExpr<<expr::Cmp as Panic>::Source, <expr::Cmp as Panic>::Source>;
+	type VTable = expr::ChainExpr<&'static <expr::Cmp as Panic>::VTable, &'static <expr::Cmp as Panic>::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		(self.lhs, self.rhs, ())
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, _: Self::Cmp) -> Self {
+		Self { lhs, rhs }
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		self.explain_held("Assertion failed", source, result, vtable, f)?;
+		for (held, cmp) in [(result.lhs, &source.lhs), (result.rhs, &source.rhs)] {
+			if !held {
+				f.write_str("\n- violated bound: ")?;
+				Style::BOLD.paint(f, |f| write!(f, "{} {} {}", cmp.lhs, cmp.cmp, cmp.rhs))?;
+			}
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		_: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		let expr::ChainExpr { lhs, rhs } = source;
+		write!(f, "{header}: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{} {} {} {} {}", lhs.lhs, lhs.cmp, lhs.rhs, rhs.cmp, rhs.rhs))?;
+		write!(f, "\n- {} = ", lhs.lhs)?;
+		Style::GREEN.paint(f, |f| (vtable.lhs.lhs)(self.lhs.lhs, f))?;
+		write!(f, "\n- {} = ", lhs.rhs)?;
+		Style::RED.paint(f, |f| (vtable.lhs.rhs)(self.lhs.rhs, f))?;
+		write!(f, "\n- {} = ", rhs.rhs)?;
+		Style::GREEN.paint(f, |f| (vtable.rhs.rhs)(self.rhs.rhs, f))
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let expr::ChainExpr { lhs, rhs } = source;
+		Report {
+			children: alloc::vec![self.lhs.report(lhs, result.lhs, vtable.lhs), self.rhs.report(rhs, result.rhs, vtable.rhs)],
+			..Report::leaf(
+				ReportKind::Chain,
+				alloc::format!("{} {} {} {} {}", lhs.lhs, lhs.cmp, lhs.rhs, rhs.cmp, rhs.rhs),
+				Self::reduce(result),
+			)
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		expr::ChainExpr {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		result.lhs & result.rhs
+	}
+}
+
+impl<L: Test<Panic = expr::Cmp>, R: Test<Panic = expr::Cmp>> Test for expr::ChainExpr<L, R> {
+	type Panic = expr::ChainExpr<expr::Cmp, expr::Cmp>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::ChainExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.lhs.test() & self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+impl<L: Panic, R: Panic> Panic for expr::AndExpr<L, R> {
+	type Cmp = expr::AndExpr<L::Cmp, R::Cmp>;
+	type Lhs = expr::AndExpr<L::Lhs, R::Lhs>;
+	type Result = expr::AndExpr<L::Result, R::Result>;
+	type Rhs = expr::AndExpr<L::Rhs, R::Rhs>;
+	type Source = expr::ListSource<&'static L::Source, &'static R::Source>;
+	type VTable = expr::AndExpr<&'static L::VTable, &'static R::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let lhs = self.lhs.into_parts();
+		let rhs = self.rhs.into_parts();
+
+		(
+			expr::AndExpr { lhs: lhs.0, rhs: rhs.0 },
+			expr::AndExpr { lhs: lhs.1, rhs: rhs.1 },
+			expr::AndExpr { lhs: lhs.2, rhs: rhs.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
+			rhs: R::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let lhs_eval = L::reduce(result.lhs);
+		let rhs_eval = R::reduce(result.rhs);
+		if !(lhs_eval && rhs_eval) {
+			if !lhs_eval {
+				self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
+				if !rhs_eval {
+					f.write_str("\n")?;
+				}
+			}
+			if !rhs_eval {
+				self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		self.lhs.explain_held(header, source.lhs, result.lhs, vtable.lhs, f)?;
+		f.write_str("\n")?;
+		self.rhs.explain_held(header, source.rhs, result.rhs, vtable.rhs, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		Report::node(
+			ReportKind::All,
+			"all",
+			Self::reduce(result),
+			self.lhs.report(source.lhs, result.lhs, vtable.lhs),
+			self.rhs.report(source.rhs, result.rhs, vtable.rhs),
+			source.tail,
+		)
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		L::reduce(result.lhs) && R::reduce(result.rhs)
+	}
+}
+
+impl<L: Test, R: Test> Test for expr::AndExpr<L, R> {
+	type Panic = expr::AndExpr<L::Panic, R::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::AndExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.lhs.test() & self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+impl<L: Panic, R: Panic> Panic for expr::OrExpr<L, R> {
+	type Cmp = expr::OrExpr<L::Cmp, R::Cmp>;
+	type Lhs = expr::OrExpr<L::Lhs, R::Lhs>;
+	type Result = expr::OrExpr<L::Result, R::Result>;
+	type Rhs = expr::OrExpr<L::Rhs, R::Rhs>;
+	type Source = expr::ListSource<&'static L::Source, &'static R::Source>;
+	type VTable = expr::OrExpr<&'static L::VTable, &'static R::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let lhs = self.lhs.into_parts();
+		let rhs = self.rhs.into_parts();
+
+		(
+			expr::OrExpr { lhs: lhs.0, rhs: rhs.0 },
+			expr::OrExpr { lhs: lhs.1, rhs: rhs.1 },
+			expr::OrExpr { lhs: lhs.2, rhs: rhs.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
+			rhs: R::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let lhs_eval = L::reduce(result.lhs);
+		let rhs_eval = R::reduce(result.rhs);
+		if !(lhs_eval || rhs_eval) {
+			self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
+			f.write_str("\n")?;
+			self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		let lhs_eval = L::reduce(result.lhs);
+		let rhs_eval = R::reduce(result.rhs);
+		if lhs_eval {
+			self.lhs.explain_held(header, source.lhs, result.lhs, vtable.lhs, f)?;
+			if rhs_eval {
+				f.write_str("\n")?;
+			}
+		}
+		if rhs_eval {
+			self.rhs.explain_held(header, source.rhs, result.rhs, vtable.rhs, f)?;
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		Report::node(
+			ReportKind::Any,
+			"any",
+			Self::reduce(result),
+			self.lhs.report(source.lhs, result.lhs, vtable.lhs),
+			self.rhs.report(source.rhs, result.rhs, vtable.rhs),
+			source.tail,
+		)
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		L::reduce(result.lhs) || R::reduce(result.rhs)
+	}
+}
+
+impl<L: Test, R: Test> Test for expr::OrExpr<L, R> {
+	type Panic = expr::OrExpr<L::Panic, R::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::OrExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.lhs.test() | self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+/// Condition that is only evaluated if needed, which is `None` and counts as not holding if it was
+/// not evaluated.
+impl<T: Panic> Panic for Option<T> {
+	type Cmp = Option<T::Cmp>;
+	type Lhs = Option<T::Lhs>;
+	type Result = Option<T::Result>;
+	type Rhs = Option<T::Rhs>;
+	type Source = expr::LazySource<&'static T::Source>;
+	type VTable = T::VTable;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		match self.map(T::into_parts) {
+			Some((lhs, rhs, cmp)) => (Some(lhs), Some(rhs), Some(cmp)),
+			None => (None, None, None),
+		}
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		match (lhs, rhs, cmp) {
+			(Some(lhs), Some(rhs), Some(cmp)) => Some(T::from_parts(lhs, rhs, cmp)),
+			_ => None,
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		match (self, result) {
+			(Some(inner), Some(result)) => inner.explain(source.inner, result, vtable, f),
+			_ => Ok(()),
+		}
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		match (self, result) {
+			(Some(inner), Some(result)) => inner.explain_held(header, source.inner, result, vtable, f),
+			_ => Ok(()),
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		match (self, result) {
+			(Some(inner), Some(result)) => inner.report(source.inner, result, vtable),
+			_ => Report::leaf(ReportKind::Bool, source.source.into(), false),
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		self.as_ref().map(|inner| inner.test(vtable))
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		result.is_some_and(T::reduce)
+	}
+}
+
+impl<T: Test> Test for Option<T> {
+	type Panic = Option<T::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = T::VTABLE;
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.as_ref().is_some_and(T::test)
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		self.map(T::as_dyn)
+	}
+}
+
+impl<L: Panic, R: Panic> Panic for expr::LazyAndExpr<L, Option<R>> {
+	type Cmp = expr::LazyAndExpr<L::Cmp, Option<R::Cmp>>;
+	type Lhs = expr::LazyAndExpr<L::Lhs, Option<R::Lhs>>;
+	type Result = expr::LazyAndExpr<L::Result, Option<R::Result>>;
+	type Rhs = expr::LazyAndExpr<L::Rhs, Option<R::Rhs>>;
+	type Source = expr::ListSource<&'static L::Source, &'static expr::LazySource<&'static R::Source>>;
+	type VTable = expr::LazyAndExpr<&'static L::VTable, &'static R::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let lhs = self.lhs.into_parts();
+		let rhs = self.rhs.into_parts();
+
+		(
+			expr::LazyAndExpr { lhs: lhs.0, rhs: rhs.0 },
+			expr::LazyAndExpr { lhs: lhs.1, rhs: rhs.1 },
+			expr::LazyAndExpr { lhs: lhs.2, rhs: rhs.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
+			rhs: Option::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		// the right operand was only evaluated if the left one held
+		if !L::reduce(result.lhs) {
+			self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)
+		} else {
+			self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)
+		}
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		if Self::reduce(result) {
+			self.lhs.explain_held(header, source.lhs, result.lhs, vtable.lhs, f)?;
+			f.write_str("\n")?;
+			self.rhs.explain_held(header, source.rhs, result.rhs, vtable.rhs, f)?;
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let lhs = self.lhs.report(source.lhs, result.lhs, vtable.lhs);
+		let rhs = self.rhs.report(source.rhs, result.rhs, vtable.rhs);
+		let mut text = if lhs.kind == ReportKind::Any {
+			alloc::format!("({})", lhs.source)
+		} else {
+			lhs.source.clone()
+		};
+		text.push_str(" && ");
+		text.push_str(source.rhs.source);
+
+		let evaluated = lhs.passed;
+		let mut children = alloc::vec![lhs];
+		if evaluated {
+			if source.tail {
+				children.extend(rhs.children);
+			} else {
+				children.push(rhs);
+			}
+		}
+		Report {
+			children,
+			..Report::leaf(ReportKind::All, text, Self::reduce(result))
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		L::reduce(result.lhs) && <Option<R> as Panic>::reduce(result.rhs)
+	}
+}
+
+impl<L: Test, R: Test> Test for expr::LazyAndExpr<L, Option<R>> {
+	type Panic = expr::LazyAndExpr<L::Panic, Option<R::Panic>>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::LazyAndExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.lhs.test() && self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+impl<L: Panic, R: Panic> Panic for expr::LazyOrExpr<L, Option<R>> {
+	type Cmp = expr::LazyOrExpr<L::Cmp, Option<R::Cmp>>;
+	type Lhs = expr::LazyOrExpr<L::Lhs, Option<R::Lhs>>;
+	type Result = expr::LazyOrExpr<L::Result, Option<R::Result>>;
+	type Rhs = expr::LazyOrExpr<L::Rhs, Option<R::Rhs>>;
+	type Source = expr::ListSource<&'static L::Source, &'static expr::LazySource<&'static R::Source>>;
+	type VTable = expr::LazyOrExpr<&'static L::VTable, &'static R::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let lhs = self.lhs.into_parts();
+		let rhs = self.rhs.into_parts();
+
+		(
+			expr::LazyOrExpr { lhs: lhs.0, rhs: rhs.0 },
+			expr::LazyOrExpr { lhs: lhs.1, rhs: rhs.1 },
+			expr::LazyOrExpr { lhs: lhs.2, rhs: rhs.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
+			rhs: Option::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		if !Self::reduce(result) {
+			self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
+			f.write_str("\n")?;
+			self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		// the right operand was only evaluated if the left one did not hold
+		if L::reduce(result.lhs) {
+			self.lhs.explain_held(header, source.lhs, result.lhs, vtable.lhs, f)
+		} else {
+			self.rhs.explain_held(header, source.rhs, result.rhs, vtable.rhs, f)
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let lhs = self.lhs.report(source.lhs, result.lhs, vtable.lhs);
+		let rhs = self.rhs.report(source.rhs, result.rhs, vtable.rhs);
+		let mut text = lhs.source.clone();
+		text.push_str(" || ");
+		text.push_str(source.rhs.source);
+
+		let evaluated = !lhs.passed;
+		let mut children = alloc::vec![lhs];
+		if evaluated {
+			if source.tail {
+				children.extend(rhs.children);
+			} else {
+				children.push(rhs);
+			}
+		}
+		Report {
+			children,
+			..Report::leaf(ReportKind::Any, text, Self::reduce(result))
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		L::reduce(result.lhs) || <Option<R> as Panic>::reduce(result.rhs)
+	}
+}
+
+impl<L: Test, R: Test> Test for expr::LazyOrExpr<L, Option<R>> {
+	type Panic = expr::LazyOrExpr<L::Panic, Option<R::Panic>>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::LazyOrExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.lhs.test() || self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+impl<I: Panic> Panic for expr::NotExpr<I> {
+	type Cmp = expr::NotExpr<I::Cmp>;
+	type Lhs = expr::NotExpr<I::Lhs>;
+	type Result = expr::NotExpr<I::Result>;
+	type Rhs = expr::NotExpr<I::Rhs>;
+	type Source = expr::NotExpr<&'static I::Source>;
+	type VTable = expr::NotExpr<&'static I::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let inner = self.inner.into_parts();
+		(
+			expr::NotExpr { inner: inner.0 },
+			expr::NotExpr { inner: inner.1 },
+			expr::NotExpr { inner: inner.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			inner: I::from_parts(lhs.inner, rhs.inner, cmp.inner),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		if I::reduce(result.inner) {
+			self.inner.explain_held("Unexpectedly held", source.inner, result.inner, vtable.inner, f)?;
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		_: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		self.inner.explain(source.inner, result.inner, vtable.inner, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let inner = self.inner.report(source.inner, result.inner, vtable.inner);
+		let source = alloc::format!("not({})", inner.source);
+		Report {
+			children: alloc::vec![inner],
+			..Report::leaf(ReportKind::Not, source, Self::reduce(result))
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			inner: self.inner.test(vtable.inner),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		!I::reduce(result.inner)
+	}
+}
+
+impl<I: Test> Test for expr::NotExpr<I> {
+	type Panic = expr::NotExpr<I::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const { expr::NotExpr { inner: I::VTABLE } };
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		!self.inner.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			inner: self.inner.as_dyn(),
+		}
+	}
+}
+
+impl<L: Panic, R: Panic> Panic for expr::ImpliesExpr<L, R> {
+	type Cmp = expr::ImpliesExpr<L::Cmp, R::Cmp>;
+	type Lhs = expr::ImpliesExpr<L::Lhs, R::Lhs>;
+	type Result = expr::ImpliesExpr<L::Result, R::Result>;
+	type Rhs = expr::ImpliesExpr<L::Rhs, R::Rhs>;
+	type Source = expr::ImpliesExpr<&'static L::Source, &'static R::Source>;
+	type VTable = expr::ImpliesExpr<&'static L::VTable, &'static R::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let lhs = self.lhs.into_parts();
+		let rhs = self.rhs.into_parts();
+
+		(
+			expr::ImpliesExpr { lhs: lhs.0, rhs: rhs.0 },
+			expr::ImpliesExpr { lhs: lhs.1, rhs: rhs.1 },
+			expr::ImpliesExpr { lhs: lhs.2, rhs: rhs.2 },
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
+			rhs: R::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		if !Self::reduce(result) {
+			self.lhs.explain_held("Premise held", source.lhs, result.lhs, vtable.lhs, f)?;
+			f.write_str("\n")?;
+			self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+		}
+		Ok(())
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		if R::reduce(result.rhs) {
+			self.rhs.explain_held(header, source.rhs, result.rhs, vtable.rhs, f)
+		} else {
+			self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let lhs = self.lhs.report(source.lhs, result.lhs, vtable.lhs);
+		let rhs = self.rhs.report(source.rhs, result.rhs, vtable.rhs);
+		let source = alloc::format!("implies({}, {})", lhs.source, rhs.source);
+		// the conclusion is only evaluated if the premise held
+		let children = if lhs.passed { alloc::vec![lhs, rhs] } else { alloc::vec![lhs] };
+		Report {
+			children,
+			..Report::leaf(ReportKind::Implies, source, Self::reduce(result))
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		Self::Result {
+			lhs: self.lhs.test(vtable.lhs),
+			rhs: self.rhs.test(vtable.rhs),
+		}
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		!L::reduce(result.lhs) || R::reduce(result.rhs)
+	}
+}
+
+impl<L: Test, R: Test> Test for expr::ImpliesExpr<L, R> {
+	type Panic = expr::ImpliesExpr<L::Panic, R::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
+		expr::ImpliesExpr {
+			lhs: L::VTABLE,
+			rhs: R::VTABLE,
+		}
+	};
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		!self.lhs.test() | self.rhs.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			lhs: self.lhs.as_dyn(),
+			rhs: self.rhs.as_dyn(),
+		}
+	}
+}
+
+impl<I: Panic> Panic for expr::MessageExpr<expr::LazyMessage, I> {
+	type Cmp = expr::MessageExpr<expr::LazyMessage, I::Cmp>;
+	type Lhs = I::Lhs;
+	type Result = I::Result;
+	type Rhs = I::Rhs;
+	type Source = I::Source;
+	type VTable = I::VTable;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let (lhs, rhs, cmp) = self.inner.into_parts();
+		(
+			lhs,
+			rhs,
+			expr::MessageExpr {
+				message: self.message,
+				inner: cmp,
+			},
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			message: cmp.message,
+			inner: I::from_parts(lhs, rhs, cmp.inner),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "{}", self.message)?;
+		self.inner.explain(source, result, vtable, f)
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		self.inner.explain_held(header, source, result, vtable, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let report = self.inner.report(source, result, vtable);
+		Report {
+			message: (!report.passed).then(|| alloc::format!("{}", self.message)),
+			..report
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		self.inner.test(vtable)
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		I::reduce(result)
+	}
+}
+
+impl<I: Test> Test for expr::MessageExpr<expr::LazyMessage, I> {
+	type Panic = expr::MessageExpr<expr::LazyMessage, I::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = I::VTABLE;
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.inner.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			message: self.message,
+			inner: self.inner.as_dyn(),
+		}
+	}
+}
+
+impl<I: Panic> Panic for expr::LabelExpr<(), I> {
+	type Cmp = I::Cmp;
+	type Lhs = I::Lhs;
+	type Result = I::Result;
+	type Rhs = I::Rhs;
+	type Source = expr::LabelExpr<&'static str, &'static I::Source>;
+	type VTable = I::VTable;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		self.inner.into_parts()
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
+		Self {
+			label: (),
+			inner: I::from_parts(lhs, rhs, cmp),
+		}
+	}
+
+	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		Style::BOLD.paint(f, |f| write!(f, "[{}]", source.label))?;
+		f.write_str("\n")?;
+		self.inner.explain(source.inner, result, vtable, f)
+	}
+
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		Style::BOLD.paint(f, |f| write!(f, "[{}]", source.label))?;
+		f.write_str("\n")?;
+		self.inner.explain_held(header, source.inner, result, vtable, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		Report {
+			label: Some(source.label),
+			..self.inner.report(source.inner, result, vtable)
+		}
+	}
+
+	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
+		self.inner.test(vtable)
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		I::reduce(result)
+	}
+}
+
+impl<I: Test> Test for expr::LabelExpr<(), I> {
+	type Panic = expr::LabelExpr<(), I::Panic>;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = I::VTABLE;
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.inner.test()
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Self::Panic {
+			label: (),
+			inner: self.inner.as_dyn(),
+		}
+	}
+}
+
+impl Panic for () {
+	type Cmp = ();
+	type Lhs = ();
+	type Result = ();
+	type Rhs = ();
+	type Source = ();
+	type VTable = ();
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		((), (), ())
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(_: Self::Lhs, _: Self::Rhs, _: Self::Cmp) -> Self {}
+
+	fn explain(self, _: &Self::Source, _: Self::Result, _: &'static Self::VTable, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+
+	fn explain_held(self, _: &str, _: &Self::Source, _: Self::Result, _: &'static Self::VTable, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, _: &Self::Source, _: Self::Result, _: &'static Self::VTable) -> Report {
+		Report::leaf(ReportKind::All, "all()".into(), true)
+	}
+
+	fn test(&self, _: &'static Self::VTable) -> Self::Result {}
+
+	fn reduce(_: Self::Result) -> bool {
+		true
+	}
+}
+
+impl Test for () {
+	type Panic = ();
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &();
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		true
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {}
+}
+
+/// List of conditions, stored as `AndExpr { lhs: head, rhs: tail }` and terminated by `()`.
+#[doc(hidden)]
+pub trait PanicList: Panic {
+	const LEN: usize;
+
+	fn count(result: Self::Result) -> usize;
+	/// Explains the conditions that held if `held` is true, or those that failed otherwise.
+	fn explain_each(
+		self,
+		held: bool,
+		first: bool,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result;
+	#[cfg(feature = "alloc")]
+	fn report_each(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, reports: &mut alloc::vec::Vec<Report>);
+}
+
+#[doc(hidden)]
+pub trait TestList: Test<Panic: PanicList> {
+	fn count(&self) -> usize;
+}
+
+impl PanicList for () {
+	const LEN: usize = 0;
+
+	#[inline(always)]
+	fn count(_: Self::Result) -> usize {
+		0
+	}
+
+	fn explain_each(
+		self,
+		_: bool,
+		_: bool,
+		_: &Self::Source,
+		_: Self::Result,
+		_: &'static Self::VTable,
+		_: &mut fmt::Formatter,
+	) -> fmt::Result {
+		Ok(())
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report_each(self, _: &Self::Source, _: Self::Result, _: &'static Self::VTable, _: &mut alloc::vec::Vec<Report>) {}
+}
+
+impl TestList for () {
+	#[inline(always)]
+	fn count(&self) -> usize {
+		0
+	}
+}
+
+impl<H: Panic, T: PanicList> PanicList for expr::AndExpr<H, T> {
+	const LEN: usize = 1 + T::LEN;
+
+	#[inline(always)]
+	fn count(result: Self::Result) -> usize {
+		H::reduce(result.lhs) as usize + T::count(result.rhs)
+	}
+
+	fn explain_each(
+		self,
+		held: bool,
+		first: bool,
+		source: &Self::Source,
+		result: Self::Result,
+		vtable: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		let matches = H::reduce(result.lhs) == held;
+		if matches {
+			if !first {
+				f.write_str("\n")?;
+			}
+			if held {
+				self.lhs.explain_held("Unexpectedly held", source.lhs, result.lhs, vtable.lhs, f)?;
+			} else {
+				self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
+			}
+		}
+		self.rhs.explain_each(held, first && !matches, source.rhs, result.rhs, vtable.rhs, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report_each(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, reports: &mut alloc::vec::Vec<Report>) {
+		reports.push(self.lhs.report(source.lhs, result.lhs, vtable.lhs));
+		self.rhs.report_each(source.rhs, result.rhs, vtable.rhs, reports);
+	}
+}
+
+impl<H: Test, T: TestList> TestList for expr::AndExpr<H, T> {
+	#[inline(always)]
+	fn count(&self) -> usize {
+		self.lhs.test() as usize + self.rhs.count()
+	}
+}
+
+impl expr::CountKind {
+	#[inline(always)]
+	fn holds(self, n: usize, count: usize) -> bool {
+		match self {
+			Self::AtLeast => count >= n,
+			Self::AtMost => count <= n,
+			Self::Exactly => count == n,
+		}
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			Self::AtLeast => "at_least",
+			Self::AtMost => "at_most",
+			Self::Exactly => "exactly",
+		}
+	}
+
+	fn description(self) -> &'static str {
+		match self {
+			Self::AtLeast => "at least",
+			Self::AtMost => "at most",
+			Self::Exactly => "exactly",
+		}
+	}
+}
+
+impl<L: PanicList> expr::CountExpr<expr::CountKind, usize, L> {
+	fn explain_count(header: &str, source: &<Self as Panic>::Source, result: <Self as Panic>::Result, f: &mut fmt::Formatter) -> fmt::Result {
+		let expr::CountExpr { kind, n, list } = result;
+		write!(f, "{header}: ")?;
+		Style::BOLD.paint(f, |f| write!(f, "{}({}, ...)", kind.name(), source.n))?;
+		write!(f, "\n- expected: {} {n}", kind.description())?;
+		write!(f, "\n- held: {} of {}", L::count(list), L::LEN)
+	}
+}
+
+impl<L: PanicList> Panic for expr::CountExpr<expr::CountKind, usize, L> {
+	type Cmp = expr::CountExpr<expr::CountKind, usize, L::Cmp>;
+	type Lhs = expr::CountExpr<(), (), L::Lhs>;
+	type Result = expr::CountExpr<expr::CountKind, usize, L::Result>;
+	type Rhs = expr::CountExpr<(), (), L::Rhs>;
+	type Source = expr::CountExpr<(), &'static str, &'static L::Source>;
+	type VTable = expr::CountExpr<(), (), &'static L::VTable>;
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		let list = self.list.into_parts();
+		(
+			expr::CountExpr {
+				kind: (),
+				n: (),
+				list: list.0,
+			},
+			expr::CountExpr {
+				kind: (),
+				n: (),
+				list: list.1,
+			},
+			expr::CountExpr {
+				kind: self.kind,
+				n: self.n,
+				list: list.2,
+			},
+		)
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, rhs: Self::Rhs, cmp: Self::Cmp) -> Self {
 		Self {
-			lhs: L::from_parts(lhs.lhs, rhs.lhs, cmp.lhs),
-			rhs: R::from_parts(lhs.rhs, rhs.rhs, cmp.rhs),
+			kind: cmp.kind,
+			n: cmp.n,
+			list: L::from_parts(lhs.list, rhs.list, cmp.list),
 		}
 	}
 
 	fn explain(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
-		let lhs_eval = L::reduce(result.lhs);
-		let rhs_eval = R::reduce(result.rhs);
-		if !(lhs_eval || rhs_eval) {
-			self.lhs.explain(source.lhs, result.lhs, vtable.lhs, f)?;
-			self.rhs.explain(source.rhs, result.rhs, vtable.rhs, f)?;
+		if !Self::reduce(result) {
+			Self::explain_count("Assertion failed", source, result, f)?;
+			// too few conditions held if the count is below the bound, too many otherwise
+			let held = L::count(result.list) > result.n;
+			self.list.explain_each(held, false, source.list, result.list, vtable.list, f)?;
 		}
 		Ok(())
 	}
 
+	fn explain_held(
+		self,
+		header: &str,
+		source: &Self::Source,
+		result: Self::Result,
+		_: &'static Self::VTable,
+		f: &mut fmt::Formatter,
+	) -> fmt::Result {
+		Self::explain_count(header, source, result, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn report(self, source: &Self::Source, result: Self::Result, vtable: &'static Self::VTable) -> Report {
+		let mut children = alloc::vec::Vec::new();
+		self.list.report_each(source.list, result.list, vtable.list, &mut children);
+
+		let mut text = alloc::format!("{}({}", result.kind.name(), source.n);
+		for child in &children {
+			text.push_str(", ");
+			text.push_str(&child.source);
+		}
+		text.push(')');
+
+		let kind = match result.kind {
+			expr::CountKind::AtLeast => ReportKind::AtLeast,
+			expr::CountKind::AtMost => ReportKind::AtMost,
+			expr::CountKind::Exactly => ReportKind::Exactly,
+		};
+		Report {
+			children,
+			..Report::leaf(kind, text, Self::reduce(result))
+		}
+	}
+
 	fn test(&self, vtable: &'static Self::VTable) -> Self::Result {
 		Self::Result {
-			lhs: self.lhs.test(vtable.lhs),
-			rhs: self.rhs.test(vtable.rhs),
+			kind: self.kind,
+			n: self.n,
+			list: self.list.test(vtable.list),
 		}
 	}
 
 	fn reduce(result: Self::Result) -> bool {
-		L::reduce(result.lhs) || R::reduce(result.rhs)
+		result.kind.holds(result.n, L::count(result.list))
 	}
 }
 
-impl<L: Test, R: Test> Test for expr::OrExpr<L, R> {
-	type Panic = expr::OrExpr<L::Panic, R::Panic>;
+impl<L: TestList> Test for expr::CountExpr<expr::CountKind, usize, L> {
+	type Panic = expr::CountExpr<expr::CountKind, usize, L::Panic>;
 
 	const VTABLE: &'static <Self::Panic as Panic>::VTable = &const {
-		expr::OrExpr {
-			lhs: L::VTABLE,
-			rhs: R::VTABLE,
+		expr::CountExpr {
+			kind: (),
+			n: (),
+			list: L::VTABLE,
 		}
 	};
 
 	#[inline(always)]
 	fn test(&self) -> bool {
-		self.lhs.test() | self.rhs.test()
+		self.kind.holds(self.n, self.list.count())
 	}
 
 	#[inline(always)]
 	fn as_dyn(self) -> Self::Panic {
 		Self::Panic {
-			lhs: self.lhs.as_dyn(),
-			rhs: self.rhs.as_dyn(),
+			kind: self.kind,
+			n: self.n,
+			list: self.list.as_dyn(),
 		}
 	}
 }
@@ -694,67 +3068,537 @@ impl<L: Test, R: Test> Test for expr::OrExpr<L, R> {
 #[track_caller]
 #[inline(always)]
 pub fn do_panic<'a, T: Test>(source: &'static WithSource<<T::Panic as Panic>::Source>, test: T, fmt: impl Fmt<'a>) {
+	do_panic_with(source, test, fmt, None)
+}
+
+/// Same as [`do_panic`], but also lists the values recorded into `log` on failure.
+#[cfg(feature = "power")]
+#[track_caller]
+#[inline(always)]
+pub fn do_panic_power<'a, T: Test>(source: &'static WithSource<<T::Panic as Panic>::Source>, test: T, fmt: impl Fmt<'a>, log: &power::Log) {
+	do_panic_with(source, test, fmt, Some(log))
+}
+
+#[track_caller]
+#[inline(always)]
+fn do_panic_with<'a, T: Test>(
+	source: &'static WithSource<<T::Panic as Panic>::Source>,
+	test: T,
+	fmt: impl Fmt<'a>,
+	power: Option<&dyn fmt::Display>,
+) {
 	let success = test.test();
 	let panic = test.as_dyn();
 	let (lhs, rhs, cmp) = panic.into_parts();
 	if !success {
-		do_panic_impl::<T::Panic>(lhs, rhs, cmp, source, T::VTABLE, fmt.fmt())
+		#[cfg(feature = "std")]
+		if do_record_impl::<T::Panic>(lhs, rhs, cmp, source, T::VTABLE, fmt.fmt(), power) {
+			return;
+		}
+		do_panic_impl::<T::Panic>(lhs, rhs, cmp, source, T::VTABLE, fmt.fmt(), power)
 	}
 }
 
+/// Same as [`do_panic`], but panics even within `soft`, since the code following a failed
+/// `let pattern = value` assertion cannot use its bindings.
 #[track_caller]
-#[inline(never)]
-fn do_panic_impl<'a, P: Panic>(
+#[inline(always)]
+pub fn do_panic_let<'a, T: Test>(source: &'static WithSource<<T::Panic as Panic>::Source>, test: T, fmt: impl Fmt<'a>) -> ! {
+	let (lhs, rhs, cmp) = test.as_dyn().into_parts();
+	do_panic_impl::<T::Panic>(lhs, rhs, cmp, source, T::VTABLE, fmt.fmt(), None)
+}
+
+/// Type-erased value, formatted through its vtable entry.
+#[cfg(feature = "alloc")]
+struct ErasedValue(fn(core::mem::MaybeUninit<*const ()>, &mut fmt::Formatter<'_>) -> fmt::Result, core::mem::MaybeUninit<*const ()>);
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for ErasedValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		(self.0)(self.1, f)
+	}
+}
+
+struct Failure<'a, P: Panic> {
 	lhs: P::Lhs,
 	rhs: P::Rhs,
 	cmp: P::Cmp,
 	source: &'static WithSource<P::Source>,
 	vtable: &'static P::VTable,
-	fmt: &core::fmt::Arguments<'_>,
-) -> ! {
-	struct Debug<'a, P: Panic> {
-		lhs: P::Lhs,
-		rhs: P::Rhs,
-		cmp: P::Cmp,
-		source: &'static WithSource<P::Source>,
-		vtable: &'static P::VTable,
-		fmt: &'a core::fmt::Arguments<'a>,
-	}
-	impl<P: Panic> fmt::Debug for Debug<'_, P> {
-		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-			let Self {
-				lhs,
-				rhs,
-				cmp,
-				source,
-				vtable,
-				fmt,
-			} = self;
-
-			write!(f, "Assertion failed at {}:{}:{}\n", source.file, source.line, source.col)?;
-			if fmt.as_str() != Some("") {
-				write!(f, "{fmt:#?}\n")?;
-			}
-			let p = unsafe { P::from_parts(*lhs, *rhs, *cmp) };
+	fmt: &'a core::fmt::Arguments<'a>,
+	/// Intermediate values recorded in power-assert mode.
+	power: Option<&'a dyn fmt::Display>,
+}
 
-			let result = p.test(vtable);
-			p.explain(&source.source, result, vtable, f)
+impl<P: Panic> fmt::Debug for Failure<'_, P> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let Self {
+			lhs,
+			rhs,
+			cmp,
+			source,
+			vtable,
+			fmt,
+			power,
+		} = self;
+
+		Style::DIM.paint(f, |f| write!(f, "Assertion failed at {}:{}:{}", source.file, source.line, source.col))?;
+		f.write_str("\n")?;
+		if let Some(power) = power {
+			write!(f, "{power}")?;
+		}
+		if fmt.as_str() != Some("") {
+			writeln!(f, "{fmt}")?;
 		}
+		let p = unsafe { P::from_parts(*lhs, *rhs, *cmp) };
+
+		let result = p.test(vtable);
+		p.explain(&source.source, result, vtable, f)
 	}
+}
 
+#[track_caller]
+#[inline(never)]
+fn do_panic_impl<P: Panic>(
+	lhs: P::Lhs,
+	rhs: P::Rhs,
+	cmp: P::Cmp,
+	source: &'static WithSource<P::Source>,
+	vtable: &'static P::VTable,
+	fmt: &core::fmt::Arguments<'_>,
+	power: Option<&dyn fmt::Display>,
+) -> ! {
 	panic!(
 		"{:#?}",
-		Debug::<P> {
+		Failure::<P> {
 			lhs,
 			rhs,
 			cmp,
 			source,
 			vtable,
-			fmt
+			fmt,
+			power
 		}
 	);
 }
 
+#[cfg(feature = "std")]
+#[cold]
+#[inline(never)]
+fn do_record_impl<P: Panic>(
+	lhs: P::Lhs,
+	rhs: P::Rhs,
+	cmp: P::Cmp,
+	source: &'static WithSource<P::Source>,
+	vtable: &'static P::VTable,
+	fmt: &core::fmt::Arguments<'_>,
+	power: Option<&dyn fmt::Display>,
+) -> bool {
+	soft::record(&Failure::<P> {
+		lhs,
+		rhs,
+		cmp,
+		source,
+		vtable,
+		fmt,
+		power,
+	})
+}
+
+/// Failed condition reported by [`check!`], carrying the same explanation that [`assert!`] would
+/// have panicked with.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct AssertionFailure(alloc::boxed::Box<AssertionFailureInner>);
+
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+struct AssertionFailureInner {
+	file: &'static str,
+	line: u32,
+	col: u32,
+	explanation: alloc::string::String,
+	report: Report,
+}
+
+#[cfg(feature = "alloc")]
+impl AssertionFailure {
+	/// File containing the failed check.
+	#[inline]
+	pub fn file(&self) -> &'static str {
+		self.0.file
+	}
+
+	/// Line of the failed check.
+	#[inline]
+	pub fn line(&self) -> u32 {
+		self.0.line
+	}
+
+	/// Column of the failed check.
+	#[inline]
+	pub fn col(&self) -> u32 {
+		self.0.col
+	}
+
+	/// Full failure report, including the location, the custom message if any, and the source and
+	/// values of every failed condition.
+	#[inline]
+	pub fn explanation(&self) -> &str {
+		&self.0.explanation
+	}
+
+	/// Structured description of the evaluated condition.
+	#[inline]
+	pub fn report(&self) -> &Report {
+		&self.0.report
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for AssertionFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0.explanation)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for AssertionFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0.explanation)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for AssertionFailure {}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub fn do_check<'a, T: Test>(
+	source: &'static WithSource<<T::Panic as Panic>::Source>,
+	test: T,
+	fmt: impl Fmt<'a>,
+) -> Result<(), AssertionFailure> {
+	do_check_with(source, test, fmt, None)
+}
+
+/// Same as [`do_check`], but also lists the values recorded into `log` on failure.
+#[cfg(feature = "power")]
+#[inline(always)]
+pub fn do_check_power<'a, T: Test>(
+	source: &'static WithSource<<T::Panic as Panic>::Source>,
+	test: T,
+	fmt: impl Fmt<'a>,
+	log: &power::Log,
+) -> Result<(), AssertionFailure> {
+	do_check_with(source, test, fmt, Some(log))
+}
+
+#[cfg(feature = "alloc")]
+#[inline(always)]
+fn do_check_with<'a, T: Test>(
+	source: &'static WithSource<<T::Panic as Panic>::Source>,
+	test: T,
+	fmt: impl Fmt<'a>,
+	power: Option<&dyn fmt::Display>,
+) -> Result<(), AssertionFailure> {
+	let success = test.test();
+	let panic = test.as_dyn();
+	let (lhs, rhs, cmp) = panic.into_parts();
+	if success {
+		Ok(())
+	} else {
+		Err(do_check_impl::<T::Panic>(lhs, rhs, cmp, source, T::VTABLE, fmt.fmt(), power))
+	}
+}
+
+#[cfg(feature = "alloc")]
+#[cold]
+#[inline(never)]
+fn do_check_impl<P: Panic>(
+	lhs: P::Lhs,
+	rhs: P::Rhs,
+	cmp: P::Cmp,
+	source: &'static WithSource<P::Source>,
+	vtable: &'static P::VTable,
+	fmt: &core::fmt::Arguments<'_>,
+	power: Option<&dyn fmt::Display>,
+) -> AssertionFailure {
+	let p = unsafe { P::from_parts(lhs, rhs, cmp) };
+	let report = p.report(&source.source, p.test(vtable), vtable);
+
+	AssertionFailure(alloc::boxed::Box::new(AssertionFailureInner {
+		report,
+		file: source.file,
+		line: source.line,
+		col: source.col,
+		explanation: style::plain(|| {
+			alloc::format!(
+				"{:#?}",
+				Failure::<P> {
+					lhs,
+					rhs,
+					cmp,
+					source,
+					vtable,
+					fmt,
+					power
+				}
+			)
+		}),
+	}))
+}
+
+/// Sequence whose `==` comparisons are explained element by element.
+#[doc(hidden)]
+pub trait SliceLike {
+	type Item;
+	fn as_slice(&self) -> &[Self::Item];
+}
+
+impl<T, const N: usize> SliceLike for [T; N] {
+	type Item = T;
+
+	#[inline(always)]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+// unsized operands are passed by reference, see `Ref`
+impl<T> SliceLike for &[T] {
+	type Item = T;
+
+	#[inline(always)]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SliceLike for alloc::vec::Vec<T> {
+	type Item = T;
+
+	#[inline(always)]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+/// Selects the expression for `lhs == rhs`: the inherent `get` is only applicable to sequences,
+/// and other operands fall back to [`EqFallback::get`] through `Deref`.
+#[doc(hidden)]
+pub struct EqWrap<L, R>(pub EqFallback<L, R>);
+
+#[doc(hidden)]
+pub struct EqFallback<L, R> {
+	pub lhs: L,
+	pub rhs: R,
+}
+
+impl<L, R> core::ops::Deref for EqWrap<L, R> {
+	type Target = EqFallback<L, R>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<'a, 'b, L: SliceLike + PartialEq<R>, R: SliceLike> EqWrap<&'a L, &'b R>
+where
+	L::Item: PartialEq<R::Item> + fmt::Debug,
+	R::Item: fmt::Debug,
+{
+	#[inline(always)]
+	pub fn get(self) -> expr::SliceEqExpr<&'a L, &'b R> {
+		expr::SliceEqExpr {
+			lhs: self.0.lhs,
+			rhs: self.0.rhs,
+		}
+	}
+}
+
+impl<L: Copy, R: Copy> EqFallback<L, R> {
+	#[inline(always)]
+	pub fn get(&self) -> expr::CmpExpr<&'static Eq, L, R> {
+		expr::CmpExpr {
+			cmp: &Eq,
+			lhs: self.lhs,
+			rhs: self.rhs,
+		}
+	}
+}
+
+/// Selects the comparator of `lhs ~ rhs`: the inherent `get` is only applicable to
+/// [`cmp::approx_eq`], which stands for [`cmp::Approx::DEFAULT`], and local comparators fall back to
+/// [`TildeFallback::get`] through `Deref`.
+#[doc(hidden)]
+pub struct TildeWrap<'a, T>(pub TildeFallback<'a, T>);
+
+#[doc(hidden)]
+pub struct TildeFallback<'a, T> {
+	pub inner: &'a T,
+}
+
+impl<'a, T> core::ops::Deref for TildeWrap<'a, T> {
+	type Target = TildeFallback<'a, T>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<F: Fn() -> cmp::Approx> TildeWrap<'_, F> {
+	#[inline(always)]
+	pub fn get(self) -> &'static cmp::Approx {
+		&cmp::Approx::DEFAULT
+	}
+}
+
+impl<'a, T> TildeFallback<'a, T> {
+	#[inline(always)]
+	pub fn get(&self) -> &'a T {
+		self.inner
+	}
+}
+
+/// Captures a value for printing: the inherent `get` is only applicable if it implements `Debug`,
+/// and other values fall back to [`CaptureFallback::get`] through `Deref`.
+#[doc(hidden)]
+pub struct CaptureWrap<'a, T: ?Sized>(pub CaptureFallback<'a, T>);
+
+#[doc(hidden)]
+pub struct CaptureFallback<'a, T: ?Sized> {
+	pub inner: &'a T,
+}
+
+impl<T: ?Sized> Clone for CaptureWrap<'_, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: ?Sized> Copy for CaptureWrap<'_, T> {}
+
+impl<T: ?Sized> Clone for CaptureFallback<'_, T> {
+	#[inline(always)]
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T: ?Sized> Copy for CaptureFallback<'_, T> {}
+
+impl<'a, T: ?Sized> core::ops::Deref for CaptureWrap<'a, T> {
+	type Target = CaptureFallback<'a, T>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T: fmt::Debug> CaptureWrap<'_, T> {
+	#[inline(always)]
+	pub fn get(self) -> expr::Value {
+		expr::Value {
+			ptr: self.0.inner as *const T as *const (),
+			fmt: Some(|ptr, f| unsafe { T::fmt(&*(ptr as *const T), f) }),
+		}
+	}
+}
+
+impl<T: ?Sized> CaptureFallback<'_, T> {
+	#[inline(always)]
+	pub fn get(&self) -> expr::Value {
+		expr::Value {
+			ptr: core::ptr::null(),
+			fmt: None,
+		}
+	}
+}
+
+/// Captures the receiver of a method call before the call, which may consume it: the inherent `get`
+/// is only applicable if it implements `Copy` and `Debug`, and other values fall back to
+/// [`CaptureWrap::get`] through `Deref`.
+#[doc(hidden)]
+pub struct SnapshotWrap<'a, T: ?Sized>(pub CaptureWrap<'a, T>);
+
+impl<'a, T: ?Sized> core::ops::Deref for SnapshotWrap<'a, T> {
+	type Target = CaptureWrap<'a, T>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T: Copy + fmt::Debug> SnapshotWrap<'_, T> {
+	#[inline(always)]
+	pub fn get(self) -> expr::Copied<T> {
+		expr::Copied(*self.0 .0.inner)
+	}
+}
+
+/// Receiver captured by [`SnapshotWrap`], which is stored until the assertion is explained.
+///
+/// Values that implement `Copy` are copied, and only formatted on failure. Other values are
+/// formatted right away, since the call may move or mutate them, and are left out without `alloc`.
+#[doc(hidden)]
+pub trait Snapshot {
+	type Stored;
+
+	fn store(self) -> Self::Stored;
+	fn value(stored: &Self::Stored) -> expr::Value;
+}
+
+impl<T: Copy + fmt::Debug> Snapshot for expr::Copied<T> {
+	type Stored = T;
+
+	#[inline(always)]
+	fn store(self) -> Self::Stored {
+		self.0
+	}
+
+	#[inline(always)]
+	fn value(stored: &Self::Stored) -> expr::Value {
+		CaptureWrap(CaptureFallback { inner: stored }).get()
+	}
+}
+
+impl Snapshot for expr::Value {
+	#[cfg(feature = "alloc")]
+	type Stored = Option<alloc::string::String>;
+	#[cfg(not(feature = "alloc"))]
+	type Stored = ();
+
+	#[inline(never)]
+	fn store(self) -> Self::Stored {
+		#[cfg(feature = "alloc")]
+		return self.report("").map(|operand| operand.value);
+	}
+
+	#[inline(always)]
+	fn value(stored: &Self::Stored) -> expr::Value {
+		#[cfg(feature = "alloc")]
+		if let Some(value) = stored {
+			return expr::Value {
+				ptr: value as *const alloc::string::String as *const (),
+				fmt: Some(|ptr, f| f.write_str(unsafe { &*(ptr as *const alloc::string::String) })),
+			};
+		}
+		#[cfg(not(feature = "alloc"))]
+		let _ = stored;
+		expr::Value {
+			ptr: core::ptr::null(),
+			fmt: None,
+		}
+	}
+}
+
 pub struct Ref<'a, T: ?Sized> {
 	pub inner: &'a T,
 }
@@ -773,6 +3617,16 @@ impl<'a, T> Ref<'a, [T]> {
 	}
 }
 
+/// Right-hand side of `item in collection`, which is formatted with [`contains::Collection`].
+#[doc(hidden)]
+pub struct Searched<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized + contains::Collection> fmt::Debug for Searched<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
 #[derive(Copy, Clone)]
 pub struct WithSource<S> {
 	pub source: S,
@@ -0,0 +1,152 @@
+//! Runtime support for the `power` feature, where the assertion macros record the value of every
+//! sub-expression of an operand as it is evaluated.
+
+use crate::{expr, style::Style, CaptureFallback, CaptureWrap, Operand};
+use alloc::vec::Vec;
+use core::{
+	cell::RefCell,
+	fmt,
+	marker::PhantomData,
+	mem::{align_of, size_of, MaybeUninit},
+};
+
+/// Storage of the values that are copied into the log.
+type Buf = [usize; 2];
+
+/// Intermediate values recorded while evaluating a condition, which are listed if it fails.
+///
+/// Values that implement `Copy` and fit in two words are copied, and only formatted on failure.
+/// `'a` bounds the lifetimes they contain, so that references are still valid by then.
+pub struct Log<'a>(RefCell<Vec<Entry>>, PhantomData<fn(&'a ()) -> &'a ()>);
+
+#[doc(hidden)]
+pub struct Entry {
+	source: &'static str,
+	value: Recorded,
+}
+
+enum Recorded {
+	/// Copy of a value, formatted by `fmt`.
+	Copied(MaybeUninit<Buf>, unsafe fn(*const (), &mut fmt::Formatter<'_>) -> fmt::Result),
+	/// Value formatted as it was produced, since it may be moved or mutated by the rest of the
+	/// condition.
+	Formatted(alloc::string::String),
+}
+
+/// Value recorded into a [`Log`], see [`Log::capture`].
+#[doc(hidden)]
+pub trait Record<'a> {
+	fn record(self, source: &'static str) -> Option<Entry>;
+}
+
+/// Value whose type implements `Copy` and `Debug`.
+#[doc(hidden)]
+pub struct Copied<T>(T);
+
+impl<'a, T: Copy + fmt::Debug + 'a> Record<'a> for Copied<T> {
+	#[inline(always)]
+	fn record(self, source: &'static str) -> Option<Entry> {
+		if size_of::<T>() > size_of::<Buf>() || align_of::<T>() > align_of::<Buf>() {
+			return expr::Value {
+				ptr: &self.0 as *const T as *const (),
+				fmt: Some(|ptr, f| unsafe { T::fmt(&*(ptr as *const T), f) }),
+			}
+			.record(source);
+		}
+
+		let mut buf = MaybeUninit::<Buf>::uninit();
+		unsafe { buf.as_mut_ptr().cast::<T>().write(self.0) };
+		Some(Entry {
+			source,
+			value: Recorded::Copied(buf, |ptr, f| unsafe { T::fmt(&*(ptr as *const T), f) }),
+		})
+	}
+}
+
+impl Record<'_> for expr::Value {
+	#[inline(always)]
+	fn record(self, source: &'static str) -> Option<Entry> {
+		self.report(source).map(|Operand { source, value }| Entry {
+			source,
+			value: Recorded::Formatted(value),
+		})
+	}
+}
+
+/// Captures a value for recording: the inherent `get` is only applicable if it implements `Copy`
+/// and `Debug`, and other values fall back to [`CaptureWrap::get`] through `Deref`.
+#[doc(hidden)]
+pub struct Capture<'a, T: ?Sized>(CaptureWrap<'a, T>);
+
+impl<'a, T: ?Sized> core::ops::Deref for Capture<'a, T> {
+	type Target = CaptureWrap<'a, T>;
+
+	#[inline(always)]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T: Copy + fmt::Debug> Capture<'_, T> {
+	#[inline(always)]
+	pub fn get(self) -> Copied<T> {
+		Copied(*self.0 .0.inner)
+	}
+}
+
+impl<'a> Log<'a> {
+	#[inline]
+	pub fn new() -> Self {
+		Self(RefCell::new(Vec::new()), PhantomData)
+	}
+
+	#[inline(always)]
+	pub fn capture<'b, T: ?Sized>(&self, inner: &'b T) -> Capture<'b, T> {
+		Capture(CaptureWrap(CaptureFallback { inner }))
+	}
+
+	/// Records `value`, which is formatted right away if it cannot be copied. Values whose type
+	/// does not implement `Debug` are skipped.
+	#[inline(never)]
+	pub fn record(&self, source: &'static str, value: impl Record<'a>) {
+		self.0.borrow_mut().extend(value.record(source));
+	}
+}
+
+impl Default for Log<'_> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl fmt::Display for Log<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		struct Erased<'a>(&'a MaybeUninit<Buf>, unsafe fn(*const (), &mut fmt::Formatter<'_>) -> fmt::Result);
+		impl fmt::Debug for Erased<'_> {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				unsafe { (self.1)(self.0.as_ptr() as *const (), f) }
+			}
+		}
+
+		let entries = self.0.borrow();
+		if entries.is_empty() {
+			return Ok(());
+		}
+		f.write_str("Intermediate values:")?;
+		for Entry { source, value } in entries.iter() {
+			write!(f, "\n- {source} = ")?;
+			match value {
+				Recorded::Copied(buf, fmt) => Style::RED.paint(f, |f| write!(f, "{:#?}", Erased(buf, *fmt)))?,
+				Recorded::Formatted(value) => Style::RED.write(f, value)?,
+			}
+		}
+		f.write_str("\n")
+	}
+}
+
+/// Returns `value`, after the places read by a condition were recorded into `_places`.
+#[inline(always)]
+pub fn then<T>(_places: impl Sized, value: T) -> T {
+	value
+}
@@ -0,0 +1,225 @@
+//! Runtime support for the quantifiers `for_all(x in iter => cond)` and `exists(x in iter => cond)`.
+//!
+//! Elements are usually borrowed from the iterator only while they are tested, so the explanation
+//! of each failing element is formatted right away, up to [`MAX_ELEMENTS`] elements.
+
+use crate::{expr, style::Style, ListItem, Operand, Panic, Report, ReportKind, Test};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+/// Number of failing elements that are explained in failure reports.
+pub const MAX_ELEMENTS: usize = 5;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Quantifier {
+	ForAll,
+	Exists,
+}
+
+/// Element for which the condition of a quantifier did not hold.
+pub struct Element {
+	pub index: usize,
+	/// The element, if its type implements `Debug`.
+	pub value: Option<Operand>,
+	pub explanation: String,
+	pub report: Report,
+}
+
+/// Outcome of a quantifier over all the elements it tested.
+pub struct Quantified {
+	pub quantifier: Quantifier,
+	pub passed: bool,
+	/// Number of tested elements.
+	pub len: usize,
+	/// Number of elements for which the condition did not hold.
+	pub failed: usize,
+	/// Index of the element for which the condition of `exists` held.
+	pub witness: Option<usize>,
+	/// The first [`MAX_ELEMENTS`] failing elements.
+	pub elements: Vec<Element>,
+}
+
+/// Outcome of the condition of a quantifier on one element.
+pub enum Eval {
+	Held,
+	/// The condition failed once [`MAX_ELEMENTS`] elements were already explained.
+	Failed,
+	Explained(Box<Element>),
+}
+
+/// Tests the condition of a quantifier on one element, explaining it if it does not hold and fewer
+/// than [`MAX_ELEMENTS`] elements were `explained` so far.
+#[inline]
+pub fn eval<T: Test>(test: T, source: &'static <T::Panic as Panic>::Source, var: &'static str, value: expr::Value, explained: usize) -> Eval {
+	if test.test() {
+		Eval::Held
+	} else if explained < MAX_ELEMENTS {
+		Eval::Explained(Box::new(eval_failed(test.as_dyn(), source, T::VTABLE, var, value)))
+	} else {
+		Eval::Failed
+	}
+}
+
+#[cold]
+#[inline(never)]
+fn eval_failed<P: Panic>(panic: P, source: &'static P::Source, vtable: &'static P::VTable, var: &'static str, value: expr::Value) -> Element {
+	struct Explain<P: Panic>(P, &'static P::Source, P::Result, &'static P::VTable);
+	impl<P: Panic> fmt::Display for Explain<P> {
+		fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+			let Self(panic, source, result, vtable) = *self;
+			panic.explain(source, result, vtable, f)
+		}
+	}
+
+	let result = panic.test(vtable);
+	Element {
+		index: 0,
+		value: value.report(var),
+		explanation: crate::style::plain(|| alloc::format!("{}", Explain(panic, source, result, vtable))),
+		report: panic.report(source, result, vtable),
+	}
+}
+
+/// Holds if the condition holds for every element.
+pub fn for_all<I: IntoIterator>(iter: I, mut cond: impl FnMut(I::Item, usize) -> Eval) -> Quantified {
+	let mut q = Quantified::new(Quantifier::ForAll);
+	for (index, item) in iter.into_iter().enumerate() {
+		q.len += 1;
+		let eval = cond(item, q.elements.len());
+		q.push(index, eval);
+	}
+	q.passed = q.failed == 0;
+	q
+}
+
+/// Holds if the condition holds for at least one element, which stops the iteration.
+pub fn exists<I: IntoIterator>(iter: I, mut cond: impl FnMut(I::Item, usize) -> Eval) -> Quantified {
+	let mut q = Quantified::new(Quantifier::Exists);
+	for (index, item) in iter.into_iter().enumerate() {
+		q.len += 1;
+		match cond(item, q.elements.len()) {
+			Eval::Held => {
+				q.witness = Some(index);
+				q.passed = true;
+				break;
+			}
+			eval => q.push(index, eval),
+		}
+	}
+	q
+}
+
+impl Quantified {
+	fn new(quantifier: Quantifier) -> Self {
+		Self {
+			quantifier,
+			passed: false,
+			len: 0,
+			failed: 0,
+			witness: None,
+			elements: Vec::new(),
+		}
+	}
+
+	fn push(&mut self, index: usize, eval: Eval) {
+		match eval {
+			Eval::Held => {}
+			Eval::Failed => self.failed += 1,
+			Eval::Explained(element) => {
+				self.failed += 1;
+				self.elements.push(Element { index, ..*element });
+			}
+		}
+	}
+}
+
+/// Type-erased reference to a [`Quantified`], which outlives the failure report.
+#[derive(Copy, Clone)]
+pub struct Quant(*const Quantified);
+
+impl Panic for Quant {
+	type Cmp = ();
+	type Lhs = Self;
+	type Result = bool;
+	type Rhs = ();
+	type Source = &'static str;
+	type VTable = ();
+
+	#[inline(always)]
+	fn into_parts(self) -> (Self::Lhs, Self::Rhs, Self::Cmp) {
+		(self, (), ())
+	}
+
+	#[inline(always)]
+	unsafe fn from_parts(lhs: Self::Lhs, _: Self::Rhs, _: Self::Cmp) -> Self {
+		lhs
+	}
+
+	fn explain(self, source: &Self::Source, _: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let q = unsafe { &*self.0 };
+		f.write_str("Assertion failed: ")?;
+		Style::BOLD.write(f, source)?;
+		match q.quantifier {
+			Quantifier::ForAll => write!(f, "\n- the condition failed for {} of {} elements", q.failed, q.len)?,
+			Quantifier::Exists => write!(f, "\n- the condition failed for all {} elements", q.len)?,
+		}
+		for element in &q.elements {
+			let mut item = ListItem { f, started: false };
+			match &element.value {
+				Some(Operand { source, value }) => fmt::write(&mut item, format_args!("element {}: {source} = {value}\n", element.index))?,
+				None => fmt::write(&mut item, format_args!("element {}:\n", element.index))?,
+			}
+			fmt::write(&mut item, format_args!("{}", element.explanation))?;
+		}
+		if q.failed > q.elements.len() {
+			write!(f, "\n- ... and {} more", q.failed - q.elements.len())?;
+		}
+		Ok(())
+	}
+
+	fn explain_held(self, header: &str, source: &Self::Source, _: Self::Result, _: &'static Self::VTable, f: &mut fmt::Formatter) -> fmt::Result {
+		let q = unsafe { &*self.0 };
+		write!(f, "{header}: ")?;
+		Style::BOLD.write(f, source)?;
+		match (q.quantifier, q.witness) {
+			(Quantifier::Exists, Some(index)) => write!(f, "\n- the condition held for element {index}"),
+			_ => write!(f, "\n- the condition held for all {} elements", q.len),
+		}
+	}
+
+	fn report(self, source: &Self::Source, result: Self::Result, _: &'static Self::VTable) -> Report {
+		let q = unsafe { &*self.0 };
+		let kind = match q.quantifier {
+			Quantifier::ForAll => ReportKind::ForAll,
+			Quantifier::Exists => ReportKind::Exists,
+		};
+		Report {
+			children: q.elements.iter().map(|element| element.report.clone()).collect(),
+			..Report::leaf(kind, (*source).into(), result)
+		}
+	}
+
+	fn test(&self, _: &'static Self::VTable) -> Self::Result {
+		unsafe { (*self.0).passed }
+	}
+
+	fn reduce(result: Self::Result) -> bool {
+		result
+	}
+}
+
+impl Test for &Quantified {
+	type Panic = Quant;
+
+	const VTABLE: &'static <Self::Panic as Panic>::VTable = &();
+
+	#[inline(always)]
+	fn test(&self) -> bool {
+		self.passed
+	}
+
+	#[inline(always)]
+	fn as_dyn(self) -> Self::Panic {
+		Quant(self)
+	}
+}
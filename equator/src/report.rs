@@ -0,0 +1,131 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Kind of a node in a [`Report`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReportKind {
+	/// `all(...)`, holds if every child holds.
+	All,
+	/// `any(...)`, holds if at least one child holds.
+	Any,
+	/// `not(...)`, holds if its only child does not hold.
+	Not,
+	/// `implies(premise, conclusion)`, holds if the premise does not hold or the conclusion holds.
+	Implies,
+	/// `at_least(n, ...)`, holds if at least `n` children hold.
+	AtLeast,
+	/// `at_most(n, ...)`, holds if at most `n` children hold.
+	AtMost,
+	/// `exactly(n, ...)`, holds if exactly `n` children hold.
+	Exactly,
+	/// `for_all(x in iter => cond)`, whose children are the failing elements, up to
+	/// [`MAX_ELEMENTS`](crate::quant::MAX_ELEMENTS).
+	ForAll,
+	/// `exists(x in iter => cond)`, whose children are the failing elements, up to
+	/// [`MAX_ELEMENTS`](crate::quant::MAX_ELEMENTS).
+	Exists,
+	/// Binary comparison, such as `a < b` or `a :cmp: b`.
+	Cmp,
+	/// Comparison chain, such as `lo <= x < hi`, whose children are the comparison with each bound.
+	Chain,
+	/// Predicate method call, such as `set.contains(&key)`.
+	Call,
+	/// Pattern match, such as `res matches Err(_)`.
+	Match,
+	/// Opaque boolean condition.
+	Bool,
+}
+
+/// Operand of a comparison in a [`Report`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operand {
+	/// Source text of the operand.
+	pub source: &'static str,
+	/// `Debug` representation of the operand's value.
+	pub value: String,
+}
+
+/// Structured description of an evaluated condition, mirroring the shape of the asserted
+/// expression.
+///
+/// Every node of the condition is present, whether it held or not, except for the operands of `&&`
+/// and `||` and the conclusions of `implies(...)` that were not evaluated.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+	/// Kind of the node.
+	pub kind: ReportKind,
+	/// Source text of the node.
+	pub source: String,
+	/// Label of the node, given as `"label" => cond`.
+	pub label: Option<&'static str>,
+	/// Message of the node, given as `(cond, "fmt", args...)`, if the node failed.
+	pub message: Option<String>,
+	/// Comparison operator, for [`ReportKind::Cmp`] nodes.
+	pub op: Option<&'static str>,
+	/// Left operand, for [`ReportKind::Cmp`] nodes, receiver, for [`ReportKind::Call`] nodes, or
+	/// matched value, for [`ReportKind::Match`] nodes.
+	pub lhs: Option<Operand>,
+	/// Right operand, for [`ReportKind::Cmp`] nodes.
+	pub rhs: Option<Operand>,
+	/// Captured arguments, for [`ReportKind::Call`] nodes.
+	pub args: Vec<Operand>,
+	/// Whether the condition held.
+	pub passed: bool,
+	/// Sub-conditions of the node.
+	pub children: Vec<Report>,
+}
+
+impl Report {
+	pub(crate) fn leaf(kind: ReportKind, source: String, passed: bool) -> Self {
+		Self {
+			kind,
+			source,
+			label: None,
+			message: None,
+			op: None,
+			lhs: None,
+			rhs: None,
+			args: Vec::new(),
+			passed,
+			children: Vec::new(),
+		}
+	}
+
+	/// Builds a node of the given kind from its children, merging the right child into it if it is
+	/// the rest of the list, since `all(a, b, c)` is stored as `all(a, all(b, c))`.
+	pub(crate) fn node(kind: ReportKind, name: &str, passed: bool, lhs: Report, rhs: Report, tail: bool) -> Self {
+		let mut children = alloc::vec![lhs];
+		if tail {
+			children.extend(rhs.children);
+		} else {
+			children.push(rhs);
+		}
+
+		let mut source = String::from(name);
+		source.push('(');
+		for (i, child) in children.iter().enumerate() {
+			if i > 0 {
+				source.push_str(", ");
+			}
+			source.push_str(&child.source);
+		}
+		source.push(')');
+
+		Self {
+			children,
+			..Self::leaf(kind, source, passed)
+		}
+	}
+
+	/// Returns an iterator over this node and all of its descendants, in depth-first order.
+	pub fn iter(&self) -> impl Iterator<Item = &Report> {
+		let mut stack = alloc::vec![self];
+		core::iter::from_fn(move || {
+			let node = stack.pop()?;
+			stack.extend(node.children.iter().rev());
+			Some(node)
+		})
+	}
+}
@@ -0,0 +1,85 @@
+use core::cell::RefCell;
+use core::fmt;
+use core::marker::PhantomData;
+use std::string::String;
+use std::vec::Vec;
+
+std::thread_local! {
+	static FAILURES: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Records the failed assertion if a soft scope is active on the current thread, and returns
+/// whether it did.
+pub(crate) fn record(failure: &dyn fmt::Debug) -> bool {
+	FAILURES.with_borrow_mut(|failures| match failures {
+		Some(failures) => {
+			failures.push(crate::style::plain(|| std::format!("{failure:#?}")));
+			true
+		},
+		None => false,
+	})
+}
+
+/// Guard that makes failing [`assert!`](crate::assert)s on the current thread record their
+/// explanation instead of panicking.
+///
+/// When the guard is dropped, it panics once with every recorded failure, in order. Within another
+/// guard, the failures are passed on to it instead, and if the thread is already panicking, they are
+/// printed to stderr.
+#[must_use = "failures are only collected while the guard is alive"]
+pub struct SoftGuard {
+	outer: Option<Vec<String>>,
+	__marker: PhantomData<*const ()>,
+}
+
+impl SoftGuard {
+	/// Starts collecting failed assertions on the current thread.
+	pub fn new() -> Self {
+		Self {
+			outer: FAILURES.replace(Some(Vec::new())),
+			__marker: PhantomData,
+		}
+	}
+
+	/// Returns the number of failures recorded so far.
+	pub fn failure_count(&self) -> usize {
+		FAILURES.with_borrow(|failures| failures.as_ref().map_or(0, Vec::len))
+	}
+}
+
+impl Default for SoftGuard {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Drop for SoftGuard {
+	fn drop(&mut self) {
+		let mut failures = FAILURES.take().unwrap_or_default();
+		if let Some(outer) = &mut self.outer {
+			// the enclosing guard reports them along with its own
+			outer.append(&mut failures);
+		}
+		FAILURES.set(self.outer.take());
+
+		if failures.is_empty() {
+			return;
+		}
+		let report = std::format!("{} soft assertion(s) failed\n\n{}", failures.len(), failures.join("\n\n"));
+		if std::thread::panicking() {
+			// panicking again would abort, so the failures are printed rather than lost
+			std::eprintln!("{report}");
+		} else {
+			panic!("{report}");
+		}
+	}
+}
+
+/// Runs `f`, recording every failed [`assert!`](crate::assert) instead of panicking, then panics
+/// once with all the failures if there were any.
+pub fn soft<R>(f: impl FnOnce() -> R) -> R {
+	let guard = SoftGuard::new();
+	let r = f();
+	drop(guard);
+	r
+}
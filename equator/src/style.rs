@@ -0,0 +1,80 @@
+use core::fmt;
+
+#[derive(Copy, Clone)]
+pub(crate) struct Style(&'static str);
+
+impl Style {
+	pub(crate) const BOLD: Self = Self("\x1b[1m");
+	pub(crate) const DIM: Self = Self("\x1b[2m");
+	pub(crate) const GREEN: Self = Self("\x1b[32m");
+	pub(crate) const RED: Self = Self("\x1b[31m");
+
+	/// Runs `inner`, wrapping its output with the escape codes of `self` if colors are enabled.
+	#[inline]
+	pub(crate) fn paint(self, f: &mut fmt::Formatter<'_>, inner: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result) -> fmt::Result {
+		if enabled() {
+			f.write_str(self.0)?;
+			inner(f)?;
+			f.write_str("\x1b[0m")
+		} else {
+			inner(f)
+		}
+	}
+
+	/// Writes `s`, wrapped with the escape codes of `self` if colors are enabled.
+	#[inline]
+	pub(crate) fn write(self, f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+		self.paint(f, |f| f.write_str(s))
+	}
+}
+
+/// Runs `inner` with styling disabled on the current thread, for explanations that are stored as
+/// data, such as the failures of `check!` and soft assertions, rather than written to the terminal.
+#[cfg(all(feature = "alloc", not(all(feature = "color", feature = "std"))))]
+#[inline(always)]
+pub(crate) fn plain<R>(inner: impl FnOnce() -> R) -> R {
+	inner()
+}
+
+#[cfg(all(feature = "color", feature = "std"))]
+pub(crate) fn plain<R>(inner: impl FnOnce() -> R) -> R {
+	struct Restore(bool);
+	impl Drop for Restore {
+		fn drop(&mut self) {
+			PLAIN.set(self.0);
+		}
+	}
+
+	let _restore = Restore(PLAIN.replace(true));
+	inner()
+}
+
+/// Without `std`, neither the terminal nor the environment can be inspected, so reports are never
+/// styled.
+#[cfg(not(all(feature = "color", feature = "std")))]
+#[inline(always)]
+fn enabled() -> bool {
+	false
+}
+
+#[cfg(all(feature = "color", feature = "std"))]
+std::thread_local! {
+	static PLAIN: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+/// Colors are forced on by `CLICOLOR_FORCE`, and otherwise only used when stderr is a terminal and
+/// `NO_COLOR` is unset.
+#[cfg(all(feature = "color", feature = "std"))]
+fn enabled() -> bool {
+	use std::io::IsTerminal;
+
+	static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+	!PLAIN.get()
+		&& *ENABLED.get_or_init(|| {
+			if std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") {
+				true
+			} else {
+				std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && std::io::stderr().is_terminal()
+			}
+		})
+}
@@ -0,0 +1,16 @@
+// helpers shared by the integration tests, each of which only uses some of them
+#![allow(dead_code)]
+
+/// Runs `f`, and returns its panic message if it panicked.
+pub fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> Option<String> {
+	let payload = std::panic::catch_unwind(f).err()?;
+	match payload.downcast_ref::<&str>() {
+		Some(msg) => Some(msg.to_string()),
+		None => Some(payload.downcast_ref::<String>().unwrap().clone()),
+	}
+}
+
+/// Runs `f`, which must panic, and returns its panic message.
+pub fn failure(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+	panic_message(f).unwrap()
+}
@@ -0,0 +1,60 @@
+use equator::{assert, assume};
+
+mod common;
+
+fn sum_prefix(v: &[u32], n: usize) -> u32 {
+	unsafe { assume!(all(n <= v.len(), !v.is_empty()), "prefix of length {n}") };
+	v[..n].iter().sum()
+}
+
+#[test]
+pub fn test_assume() {
+	let v = [1, 2, 3, 4];
+	let n = 2;
+
+	assert!(sum_prefix(&v, 3) == 6);
+	unsafe {
+		assume!(0 < n && n <= v.len());
+		assume!(any(v[0] == 1, v[1] == 1) && implies(n > 1, v.len() > 1));
+		assume!(at_least(1, n == 2, n == 3));
+		#[cfg(feature = "alloc")]
+		assume!(all(for_all(x in v => x > 0), exists(x in &v => *x == 4)));
+		assume!(all("shape" => (v.len() == 4, "length {}", v.len()), v[0] matches 1 | 2, 1 <= n < 3, n in 0..4));
+	}
+}
+
+// runs in both debug and release builds, where the right-hand side must not be evaluated either
+#[test]
+pub fn test_assume_guarded() {
+	let v = [0, 1, 2];
+	for i in 0..5 {
+		unsafe {
+			assume!(i >= v.len() || v[i] == i);
+			assume!(!(i < v.len() && v[i] != i));
+			if i < v.len() {
+				assume!(i < v.len() && v[i] == i);
+			}
+		}
+	}
+}
+
+// the messages are compared as plain text, which `color` may style
+#[cfg(all(debug_assertions, not(feature = "color")))]
+#[test]
+pub fn test_assume_debug() {
+	let v = [1, 2, 3];
+	let msg = common::failure(|| {
+		sum_prefix(&v, 4);
+	});
+	assert!(msg.contains("prefix of length 4\nAssertion failed: n <= v.len()\n- n = 4\n- v.len() = 3"));
+}
+
+#[cfg(all(debug_assertions, not(feature = "color")))]
+#[test]
+pub fn test_assume_guarded_debug() {
+	let v = [0, 1, 2];
+	let i = 3;
+	let msg = common::failure(|| unsafe { assume!(i < v.len() && v[i] == 0) });
+	assert!(msg.contains("Assertion failed: i < v.len()\n- i = 3\n- v.len() = 3"));
+	assert!(!msg.contains("v[i]"));
+}
@@ -0,0 +1,227 @@
+#![cfg(feature = "alloc")]
+
+use equator::{assert, check, AssertionFailure};
+
+fn checked_div(a: u32, b: u32) -> Result<u32, AssertionFailure> {
+	check!(all(b != 0, a >= b), "cannot divide {a} by {b}")?;
+	Ok(a / b)
+}
+
+#[test]
+pub fn test_check_ok() {
+	let x = 1;
+	let y = 2;
+	assert!(check!(all(x < y, any(x == 1, y == 1))).is_ok());
+	assert!(checked_div(4, 2).unwrap() == 2);
+}
+
+#[test]
+pub fn test_check_err() {
+	let err = checked_div(1, 0).unwrap_err();
+	assert!(err.file().ends_with("test_check.rs"));
+	assert!(err.line() == 6);
+
+	let explanation = err.to_string();
+	assert!(explanation.starts_with("Assertion failed at "));
+	assert!(explanation.contains("cannot divide 1 by 0"));
+	assert!(explanation.contains("Assertion failed: b != 0\n- b = 0\n- 0 = 0"));
+	assert!(!explanation.contains("a >= b"));
+	assert!(format!("{err:?}") == explanation);
+
+	let err = checked_div(1, 2).unwrap_err();
+	assert!(err.to_string().ends_with("Assertion failed: a >= b\n- a = 1\n- b = 2"));
+}
+
+#[test]
+pub fn test_report() {
+	use equator::{Operand, ReportKind};
+
+	let x = 1;
+	let y = 2;
+	let z = false;
+	let err = check!(all(x == y, any(x < y, z), z)).unwrap_err();
+	let report = err.report();
+
+	assert!(report.kind == ReportKind::All);
+	assert!(report.source == "all(x == y, any(x < y, z), z)");
+	assert!(!report.passed);
+	assert!(report.children.len() == 3);
+
+	let eq = &report.children[0];
+	assert!(all(eq.kind == ReportKind::Cmp, eq.op == Some("=="), !eq.passed));
+	assert!(
+		eq.lhs
+			== Some(Operand {
+				source: "x",
+				value: "1".into(),
+			})
+	);
+	assert!(
+		eq.rhs
+			== Some(Operand {
+				source: "y",
+				value: "2".into(),
+			})
+	);
+
+	let any = &report.children[1];
+	assert!(all(any.kind == ReportKind::Any, any.passed, any.children.len() == 2));
+	assert!(all(any.children[0].passed, !any.children[1].passed));
+
+	let bool = &report.children[2];
+	assert!(all(bool.kind == ReportKind::Bool, bool.source == "z", bool.lhs.is_none()));
+
+	assert!(report.iter().count() == 6);
+}
+
+#[test]
+pub fn test_report_nested() {
+	let x = 1;
+	let y = 2;
+	let err = check!(all(x == y, all(x < y, x > y))).unwrap_err();
+	let report = err.report();
+
+	// the nesting written by the user is kept
+	assert!(all(report.source == "all(x == y, all(x < y, x > y))", report.children.len() == 2));
+	assert!(all(
+		report.children[1].source == "all(x < y, x > y)",
+		report.children[1].children.len() == 2
+	));
+
+	let err = check!(any(x == y, x > y, any(x > y, y < x))).unwrap_err();
+	let report = err.report();
+	assert!(all(report.children.len() == 3, report.children[2].children.len() == 2));
+}
+
+#[test]
+pub fn test_report_lazy() {
+	use equator::ReportKind;
+
+	let x = 1;
+	let y = 2;
+	let err = check!(x < y && y < x && x == y).unwrap_err();
+	let report = err.report();
+
+	// the operands after the first one that failed were not evaluated
+	assert!(all(
+		report.kind == ReportKind::All,
+		report.source == "x < y && y < x && x == y",
+		report.children.len() == 2
+	));
+	let lt = &report.children[1];
+	assert!(all(
+		lt.kind == ReportKind::Cmp,
+		lt.source == "y < x",
+		lt.lhs.as_ref().unwrap().value == "2"
+	));
+
+	let err = check!(x == y || y < x).unwrap_err();
+	let report = err.report();
+	assert!(all(
+		report.kind == ReportKind::Any,
+		report.source == "x == y || y < x",
+		report.children.len() == 2
+	));
+
+	let err = check!(all(implies(x > y, "equal" => x == y), x == y)).unwrap_err();
+	let implies = &err.report().children[0];
+	assert!(all(
+		implies.kind == ReportKind::Implies,
+		implies.source == "implies(x > y, x == y)",
+		implies.passed,
+		implies.children.len() == 1
+	));
+}
+
+#[test]
+pub fn test_report_call() {
+	use equator::{Operand, ReportKind};
+
+	let v = vec![1, 2];
+	let x = 3;
+	let err = check!(v.contains(&x)).unwrap_err();
+	let report = err.report();
+
+	assert!(all(report.kind == ReportKind::Call, report.source == "v.contains(&x)", !report.passed));
+	assert!(report.lhs.as_ref().unwrap().source == "v");
+	assert!(
+		report.args
+			== [Operand {
+				source: "x",
+				value: "3".into(),
+			}]
+	);
+}
+
+#[test]
+pub fn test_report_match() {
+	use equator::ReportKind;
+
+	let x = Some(1);
+	let err = check!(x matches None).unwrap_err();
+	let report = err.report();
+
+	assert!(all(report.kind == ReportKind::Match, report.source == "x matches None", !report.passed));
+	assert!(report.lhs.as_ref().unwrap().value == "Some(\n    1,\n)");
+}
+
+#[test]
+pub fn test_report_chain() {
+	use equator::ReportKind;
+
+	let x = 5;
+	let err = check!(0 <= x < 5).unwrap_err();
+	let report = err.report();
+
+	assert!(all(report.kind == ReportKind::Chain, report.source == "0 <= x < 5", !report.passed));
+	assert!(report.children.len() == 2);
+	assert!(all(
+		report.children[0].passed,
+		report.children[0].source == "0 <= x",
+		!report.children[1].passed
+	));
+	assert!(report.children[1].lhs.as_ref().unwrap().value == "5");
+}
+
+#[test]
+pub fn test_report_quantifier() {
+	use equator::ReportKind;
+
+	let v = vec![1, 2, 3];
+	let err = check!(for_all(x in &v => *x != 2)).unwrap_err();
+	let report = err.report();
+
+	assert!(all(
+		report.kind == ReportKind::ForAll,
+		report.source == "for_all(x in &v => *x != 2)",
+		!report.passed
+	));
+	assert!(all(report.children.len() == 1, report.children[0].source == "* x != 2"));
+}
+
+#[test]
+pub fn test_report_label() {
+	use equator::ReportKind;
+
+	let x = 1;
+	let err = check!(all(x == 1, "positive" => x < 0, "small" => all(x < 10, x < 0))).unwrap_err();
+	let report = err.report();
+
+	assert!(all(report.kind == ReportKind::All, report.label.is_none(), report.children.len() == 3));
+	assert!(all(report.children[1].label == Some("positive"), report.children[1].source == "x < 0"));
+	assert!(all(report.children[2].kind == ReportKind::All, report.children[2].label == Some("small")));
+	assert!(report.children[2].children.len() == 2);
+}
+
+#[test]
+pub fn test_report_message() {
+	let x = 1;
+	let err = check!(all((x == 1, "x = {x}"), (x < 0, "{x} is not negative"))).unwrap_err();
+	let report = err.report();
+
+	assert!(all(
+		report.children[0].message.is_none(),
+		report.children[1].message.as_deref() == Some("1 is not negative")
+	));
+	assert!(err.to_string().contains("1 is not negative\nAssertion failed: x < 0\n"));
+}
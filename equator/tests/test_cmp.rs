@@ -0,0 +1,136 @@
+use common::failure;
+use equator::assert;
+use equator::cmp::{AbsDiff, Approx, RelDiff, Ulps};
+
+mod common;
+
+#[test]
+pub fn test_abs_diff() {
+	let x = 0.1 + 0.2;
+
+	assert!(all(x :AbsDiff::new(1e-12): 0.3, x :AbsDiff::new(0.0): x, 1.0f32 :AbsDiff::new(0.5): 1.25));
+	assert!(not(x :AbsDiff::new(1e-3): 0.31));
+	assert!(not(f64::NAN :AbsDiff::new(f64::INFINITY): f64::NAN));
+	assert!(f64::INFINITY :AbsDiff::new(0.0): f64::INFINITY);
+	assert!(not(f64::INFINITY :AbsDiff::new(f64::INFINITY): f64::NEG_INFINITY));
+}
+
+#[test]
+pub fn test_rel_diff() {
+	let tol = RelDiff::new(1e-6);
+
+	assert!(all(1e9 :tol: 1e9 + 1.0, 1e-9 :tol: 1.0000001e-9, 0.0 :tol: -0.0));
+	assert!(not(any(1.0 :tol: 1.01, 0.0 :tol: 1e-300, f64::MAX :tol: f64::INFINITY)));
+	assert!(not(f32::NAN :tol: f32::NAN));
+}
+
+#[test]
+pub fn test_ulps() {
+	let x = 1.0f64;
+	let next = f64::from_bits(x.to_bits() + 1);
+
+	assert!(all(x :Ulps::new(1): next, x :Ulps::new(0): x, 0.0 :Ulps::new(0): -0.0));
+	assert!(not(any(x :Ulps::new(0): next, f64::MAX :Ulps::new(1): f64::INFINITY, f64::NAN :Ulps::new(u64::MAX): f64::NAN)));
+	assert!(not(f64::MIN_POSITIVE :Ulps::new(u64::MAX): -f64::MIN_POSITIVE));
+	assert!(1.0f32 :Ulps::new(1): f32::from_bits(1.0f32.to_bits() + 1));
+}
+
+#[test]
+pub fn test_approx() {
+	let approx = Approx::default();
+	let loose = Approx {
+		abs: 1e-9,
+		..Approx::DEFAULT
+	};
+
+	assert!(all(0.1 + 0.2 :approx: 0.3, 1e-12 :loose: 0.0));
+	assert!(not(any(1e-12 :approx: 0.0, 1.0 :loose: 1.1)));
+}
+
+#[test]
+pub fn test_seq() {
+	let tol = AbsDiff::new(1e-3);
+	let x = [1.0, 2.0, 3.0];
+	let y = vec![1.0001, 2.0001, 3.0001];
+
+	assert!(all(x :tol: [1.0001, 2.0, 3.0], *x.as_slice() :tol: *y, [[1.0f32]] :Approx::DEFAULT: [[1.0f32]]));
+	assert!(not(any(*x.as_slice() :tol: y[..2], x :tol: [1.0, 2.0, 3.1])));
+}
+
+#[test]
+pub fn test_tilde() {
+	use equator::cmp::approx_eq;
+
+	let x = 0.1 + 0.2;
+	let y = [1.0f32, 2.0];
+	let z = [1.0f32, 2.0001];
+
+	assert!(all(x ~ 0.3, y ~ [1.0, 2.0], x ~(1e-9) 0.3, x ~(0.1) 0.35, *y.as_slice() ~(1e-3) z[..], x ~ (0.3f64).abs()));
+	assert!(not(any(x ~ 0.31, x ~(1e-3) 0.31)));
+
+	// a group right after `~` is only the tolerance if an operand follows it
+	assert!(all(x ~ (0.3), -x ~(0.5) (-0.5), x ~ (0.1) + 0.2, y.as_slice() ~(1e-3) (&z[..])));
+
+	// a local comparator takes precedence over the imported one
+	{
+		let approx_eq = AbsDiff::new(0.1);
+		assert!(all(x ~ 0.35, not(x ~ 0.5)));
+	}
+	{
+		let approx_eq = Divides;
+		assert!(all(3 ~ 9, not(2 ~ 9)));
+	}
+}
+
+#[test]
+pub fn test_tilde_failure() {
+	let x = 0.1;
+	let msg = failure(|| assert!(x ~(1e-3) 0.2));
+	assert!(msg.ends_with("Assertion failed: x ~(1e-3) 0.2\n- x = 0.1\n- 0.2 = 0.2\n- difference 0.1 exceeds tolerance 0.001"));
+
+	use equator::cmp::approx_eq;
+	let msg = failure(|| assert!(x ~ 0.2));
+	assert!(msg.contains("Assertion failed: x ~ 0.2\n- x = 0.1\n- 0.2 = 0.2\n- difference 0.1 exceeds tolerance 0\n"));
+}
+
+struct Divides;
+
+impl equator::Cmp<u32, u32> for Divides {
+	fn test(&self, lhs: &u32, rhs: &u32) -> bool {
+		rhs.is_multiple_of(*lhs)
+	}
+
+	fn explain(&self, lhs: &u32, rhs: &u32, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "remainder is {}\nquotient is {}", rhs % lhs, rhs / lhs)
+	}
+}
+
+#[test]
+pub fn test_explain() {
+	let msg = failure(|| assert!(3 :Divides: 7));
+	assert!(msg.ends_with("- 3 = 3\n- 7 = 7\n- remainder is 1\n  quotient is 2"));
+
+	let x = 0.5;
+	let msg = failure(|| assert!(x :AbsDiff::new(0.25): 1.0));
+	assert!(msg.ends_with("- 1.0 = 1.0\n- difference 0.5 exceeds tolerance 0.25"));
+
+	let msg = failure(|| assert!(x :RelDiff::new(0.25): 1.0));
+	assert!(msg.ends_with("- relative difference 0.5 exceeds tolerance 0.25"));
+
+	let msg = failure(|| assert!(1.0 :Ulps::new(1): 1.0 + 4.0 * f64::EPSILON));
+	assert!(msg.ends_with("- values are 4 ULPs apart, more than 1"));
+
+	let msg = failure(|| assert!(all(x :Approx::DEFAULT: f64::NAN, x :Ulps::new(1): -x, f32::INFINITY :AbsDiff::new(1.0): f32::MAX)));
+	assert!(msg.contains("- NaN is not equal to any value\n"));
+	assert!(msg.contains("- values have opposite signs\n"));
+	assert!(msg.ends_with("- infinite values are only equal to themselves"));
+
+	let msg = failure(|| assert!(x :Approx { abs: 0.25, rel: 0.0, ulps: 0 }: 1.0));
+	assert!(msg.ends_with(
+		"- difference 0.5 exceeds tolerance 0.25\n  relative difference 0.5 exceeds tolerance 0\n  values are 4503599627370496 ULPs apart, more than 0"
+	));
+
+	let msg = failure(|| assert!(all([1.0, 2.0] :AbsDiff::new(0.5): [1.0, 3.0], *[1.0].as_slice() :AbsDiff::new(0.5): *[1.0, 3.0].as_slice())));
+	assert!(msg.contains("- first mismatch at index 1: difference 1 exceeds tolerance 0.5\n"));
+	assert!(msg.ends_with("- lengths differ: 1 != 2"));
+}
@@ -0,0 +1,28 @@
+#![cfg(all(feature = "color", feature = "std"))]
+
+use common::failure;
+use equator::assert;
+
+mod common;
+
+#[test]
+pub fn test_color() {
+	// this is the only test in this binary, so no other thread reads the environment concurrently
+	std::env::set_var("CLICOLOR_FORCE", "1");
+
+	let x = 1;
+	let y = 2;
+	let msg = failure(|| assert!(all(x == y, any(x > y, false))));
+
+	assert!(msg.starts_with("\x1b[2mAssertion failed at "));
+	assert!(msg.contains("Assertion failed: \x1b[1mx == y\x1b[0m\n- x = \x1b[31m1\x1b[0m\n- y = \x1b[32m2\x1b[0m"));
+	assert!(msg.contains("Assertion failed: \x1b[1mx > y\x1b[0m\n- x = \x1b[31m1\x1b[0m\n- y = \x1b[32m2\x1b[0m\n"));
+	assert!(msg.contains("Assertion failed: \x1b[1mfalse\x1b[0m\n- false = \x1b[31mfalse\x1b[0m"));
+
+	// failures that are stored rather than written to the terminal are not styled
+	let err = equator::check!(x == y).unwrap_err();
+	assert!(!err.to_string().contains('\x1b'));
+
+	let msg = failure(|| equator::soft(|| assert!(x == y)));
+	assert!(msg.contains("Assertion failed: x == y\n- x = 1\n- y = 2"));
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "diff")]
+
+use common::failure;
+use equator::assert;
+
+mod common;
+
+#[test]
+pub fn test_diff() {
+	let lhs = Vec::from_iter(0..20);
+	let mut rhs = lhs.clone();
+	rhs[10] = 100;
+	rhs.push(20);
+
+	let msg = failure(|| assert!(lhs == rhs));
+	assert!(msg.ends_with(
+		"Assertion failed: lhs == rhs
+- lhs.len() = 20
+- rhs.len() = 21
+- first mismatch at index 10
+  - [10]: 10 != 100
+--- lhs
++++ rhs
+@@ -9,7 +9,7 @@
+     7,
+     8,
+     9,
+-    10,
++    100,
+     11,
+     12,
+     13,
+@@ -19,4 +19,5 @@
+     17,
+     18,
+     19,
++    20,
+ ]"
+	));
+}
+
+#[test]
+pub fn test_diff_single_line() {
+	let msg = failure(|| assert!(1 == 2));
+	assert!(msg.ends_with("Assertion failed: 1 == 2\n- 1 = 1\n- 2 = 2"));
+}
+
+#[test]
+pub fn test_diff_truncated() {
+	let lhs = Vec::from_iter(0..1000);
+	let rhs = Vec::from_iter(1000..2000);
+
+	let msg = failure(|| assert!(lhs == rhs));
+	assert!(msg.contains("- first mismatch at index 0\n  - [0]: 0 != 1000\n"));
+	assert!(msg.contains("\n  - ... and 992 more\n--- lhs\n+++ rhs\n@@ "));
+	assert!(msg.ends_with("\n... and 1968 more changed lines"));
+	assert!(msg.lines().count() < 100);
+}
@@ -0,0 +1,528 @@
+use common::failure;
+use equator::assert;
+
+mod common;
+
+#[test]
+pub fn test_not() {
+	let a = 1;
+	let b = 2;
+	let c = 3;
+
+	assert!(not(a == b));
+	assert!(all(not(any(a == b, c < a)), not(not(a < b))));
+
+	let msg = failure(|| assert!(all(not(any(a == b, a < b, c < b)), a < c)));
+	assert!(msg.ends_with("Unexpectedly held: a < b\n- a = 1\n- b = 2"));
+	assert!(!msg.contains("a == b"));
+
+	let msg = failure(|| assert!(not(not(a == b))));
+	assert!(msg.ends_with("Assertion failed: a == b\n- a = 1\n- b = 2"));
+}
+
+#[test]
+pub fn test_any() {
+	let a = 1;
+	let b = 2;
+
+	// each failed condition starts on its own line
+	let msg = failure(|| assert!(any(a == b, a > b, false)));
+	assert!(msg.ends_with(
+		"Assertion failed: a == b\n- a = 1\n- b = 2\nAssertion failed: a > b\n- a = 1\n- b = 2\nAssertion failed: false\n- false = false"
+	));
+}
+
+#[test]
+pub fn test_bool_operators() {
+	let a = 1;
+	let b = 2;
+	let c = 3;
+	let r = &&a;
+
+	assert!(a < b && b < c);
+	assert!(a > b || b < c);
+	assert!(a == 1 && **r == a || c < a);
+	assert!(a == **r && (b == 2 || c == 2));
+	assert!(**r == 1 && [true].iter().any(|&x| x || a == 0));
+
+	// the right operands are only evaluated if needed, and are explained like the left ones
+	let msg = failure(|| assert!(a == b && b < c || c < a));
+	assert!(msg.ends_with("Assertion failed: a == b\n- a = 1\n- b = 2\nAssertion failed: c < a\n- c = 3\n- a = 1"));
+
+	let msg = failure(|| assert!(a < b && (b > c || c == 0)));
+	assert!(msg.ends_with("Assertion failed: b > c\n- b = 2\n- c = 3\nAssertion failed: c == 0\n- c = 3\n- 0 = 0"));
+
+	let msg = failure(|| assert!(a < b && b < c && c < a && a == b));
+	assert!(msg.ends_with("Assertion failed: c < a\n- c = 3\n- a = 1"));
+}
+
+#[test]
+pub fn test_bool_operators_short_circuit() {
+	let v: Vec<i32> = vec![];
+	let i = 0;
+	let o = v.first().copied();
+
+	assert!(i < v.len() && v[i] == 0 || v.is_empty());
+	assert!(o.is_none() || o.unwrap() > 0);
+	assert!(o.is_some() && o.unwrap() > 0 || i in 0..1);
+
+	let msg = failure(|| assert!(i < v.len() && v[i] == 0));
+	assert!(msg.contains("Assertion failed: i < v.len()\n- i = 0\n- v.len() = 0"));
+
+	let o = [i].first().copied();
+	let msg = failure(|| assert!(o.is_none() || o.unwrap() > 0));
+	assert!(all(
+		msg.contains("Assertion failed: o.is_none()"),
+		msg.ends_with("\nAssertion failed: o.unwrap() > 0\n- o.unwrap() = 0\n- 0 = 0")
+	));
+}
+
+#[test]
+pub fn test_count() {
+	let a = 1;
+	let b = 2;
+	let c = 3;
+
+	assert!(at_least(2, a < b, b < c, c < a));
+	assert!(at_most(1, a == b, b < c, c < a));
+	assert!(exactly(1 + 1, a < b, b < c, c < a));
+	assert!(all(exactly(0, a == b), at_least(0)));
+
+	let msg = failure(|| assert!(at_least(2, a == b, b < c, c < a)));
+	assert!(msg.ends_with(
+		"Assertion failed: at_least(2, ...)
+- expected: at least 2
+- held: 1 of 3
+Assertion failed: a == b
+- a = 1
+- b = 2
+Assertion failed: c < a
+- c = 3
+- a = 1"
+	));
+
+	let n = 1;
+	let msg = failure(|| assert!(exactly(n, a < b, b < c, c < a)));
+	assert!(msg.ends_with(
+		"Assertion failed: exactly(n, ...)
+- expected: exactly 1
+- held: 2 of 3
+Unexpectedly held: a < b
+- a = 1
+- b = 2
+Unexpectedly held: b < c
+- b = 2
+- c = 3"
+	));
+}
+
+#[test]
+pub fn test_implies() {
+	let rows = 3;
+	let cols = 3;
+	let inv_rows = 3;
+	let inv_cols = 2;
+
+	assert!(implies(rows != cols, inv_rows == 0));
+	assert!(implies(rows == cols, inv_rows == rows));
+
+	let msg = failure(|| assert!(implies(rows == cols, inv_rows == inv_cols)));
+	assert!(msg.ends_with(
+		"Premise held: rows == cols
+- rows = 3
+- cols = 3
+Assertion failed: inv_rows == inv_cols
+- inv_rows = 3
+- inv_cols = 2"
+	));
+
+	// the conclusion is only evaluated if the premise holds
+	let x = [rows].first().copied();
+	let none = x.filter(|&x| x == 0);
+	assert!(implies(none.is_some(), none.unwrap() > 0));
+	assert!(implies(none.is_some(), "positive" => none.unwrap() > 0));
+	assert!(implies(none.is_some(), (none.unwrap() > 0, "{} is not positive", none.unwrap())));
+
+	let msg = failure(|| assert!(implies(x.is_some(), "small" => (x.unwrap() < 2, "{x:?} is too large"))));
+	assert!(msg.ends_with("\n[small]\nSome(3) is too large\nAssertion failed: x.unwrap() < 2\n- x.unwrap() = 3\n- 2 = 2"));
+}
+
+#[test]
+pub fn test_nested_turbofish() {
+	let a = 0usize;
+	let f = |x: usize| x + 1;
+
+	assert!(Vec::<Vec<u8>>::new().len() == a);
+	assert!(core::convert::identity::<Box<dyn Fn(usize) -> usize>>(Box::new(f))(a) > a);
+	assert!(a < Vec::<Option<u8>>::with_capacity(1).capacity());
+}
+
+#[test]
+pub fn test_method_call() {
+	use std::collections::{BTreeMap, BTreeSet};
+
+	struct Opaque(Vec<u8>);
+	impl Opaque {
+		fn is_empty(&self) -> bool {
+			self.0.is_empty()
+		}
+	}
+
+	let set = BTreeSet::from([1, 2]);
+	let map = BTreeMap::from([("a", 1)]);
+	let key = 42;
+	let path = String::from("src/lib.rs");
+	let prefix = String::from("src");
+	let opaque = Opaque(vec![]);
+
+	assert!(all(
+		set.contains(&1),
+		map.contains_key("a"),
+		path.starts_with(prefix.as_str()),
+		opaque.is_empty(),
+		!set.is_empty()
+	));
+
+	// receivers that are not `Copy` are formatted before the call, which needs `alloc`
+	#[cfg(feature = "alloc")]
+	{
+		let msg = failure(|| assert!(set.contains(&key)));
+		assert!(msg.ends_with("Assertion failed: set.contains(&key)\n- set = {\n    1,\n    2,\n}\n- key = 42"));
+
+		let msg = failure(|| assert!(path.ends_with(".md")));
+		assert!(msg.ends_with("Assertion failed: path.ends_with(\".md\")\n- path = \"src/lib.rs\"\n- \".md\" = \".md\""));
+
+		let msg = failure(|| assert!(path.starts_with(prefix.repeat(2).as_str())));
+		assert!(msg.ends_with("Assertion failed: path.starts_with(prefix.repeat(2).as_str())\n- path = \"src/lib.rs\""));
+	}
+	let msg = failure(|| assert!(set.contains(&key)));
+	assert!(msg.ends_with("- key = 42"));
+
+	let opaque = Opaque(vec![1]);
+	let msg = failure(|| assert!(opaque.is_empty()));
+	assert!(msg.ends_with("Assertion failed: opaque.is_empty()"));
+
+	let msg = failure(|| assert!(not(map.get("a").is_some())));
+	assert!(msg.ends_with("Unexpectedly held: map.get(\"a\").is_some()\n- map.get(\"a\") = Some(\n    1,\n)"));
+}
+
+#[test]
+pub fn test_method_call_receiver() {
+	#[derive(Debug)]
+	struct Counter(u32);
+	impl Counter {
+		fn bump(&mut self, max: u32) -> bool {
+			self.0 += 1;
+			self.0 <= max
+		}
+	}
+
+	#[derive(Copy, Clone, Debug)]
+	struct Flag(bool);
+	impl Flag {
+		fn get(self) -> bool {
+			self.0
+		}
+	}
+
+	let v = vec![1, 2, 3];
+	let mut it = v.iter();
+	let mut counter = Counter(0);
+	let flag = Flag(true);
+
+	assert!(all(
+		it.any(|&x| x == 2),
+		v.iter().all(|&x| x > 0),
+		counter.bump(1),
+		flag.get(),
+		Flag(true).get(),
+		v[1..].contains(&v[2]),
+		"lib.rs".ends_with(".rs"),
+		Some(&v).is_some_and(|v| v.len() == 3)
+	));
+	assert!(all(it.len() == 1, flag.0));
+
+	// the receiver is captured before the call
+	#[cfg(feature = "alloc")]
+	{
+		let msg = failure(move || assert!(counter.bump(1)));
+		assert!(msg.ends_with("Assertion failed: counter.bump(1)\n- counter = Counter(\n    1,\n)\n- 1 = 1"));
+	}
+
+	let msg = failure(|| assert!(v.first().is_none()));
+	assert!(msg.ends_with("Assertion failed: v.first().is_none()\n- v.first() = Some(\n    1,\n)"));
+}
+
+#[test]
+pub fn test_method_call_consuming() {
+	// methods taking `self` by value may consume receivers that are not `Copy`
+	let name = Some(String::from("equator"));
+	assert!(name.clone().is_some_and(|s| s.len() == 7));
+	assert!(Some(String::new()).is_some_and(|s| s.is_empty()));
+	assert!(name.is_some_and(|s| !s.is_empty()));
+
+	#[cfg(feature = "alloc")]
+	{
+		let name = Some(String::from("equator"));
+		let msg = failure(move || assert!(name.is_some_and(|s| s.is_empty())));
+		assert!(msg.ends_with("Assertion failed: name.is_some_and(|s| s.is_empty())\n- name = Some(\n    \"equator\",\n)"));
+
+		let msg = failure(|| assert!(Some(String::from("a")).is_some_and(|s| s.is_empty())));
+		assert!(msg.ends_with("- Some(String::from(\"a\")) = Some(\n    \"a\",\n)"));
+	}
+}
+
+#[test]
+pub fn test_matches() {
+	#[allow(dead_code)]
+	#[derive(Debug)]
+	enum Error {
+		Io(u32),
+		Parse,
+	}
+
+	let res: Result<u32, Error> = Err(Error::Io(2));
+	let x = Some(4);
+
+	assert!(all(
+		res matches Err(Error::Io(_)),
+		x matches Some(n) if *n > 3,
+		4 matches 1..=5,
+		x.map(|n| n + 1) matches Some(5) | None,
+		not(res matches Ok(_) | Err(Error::Parse))
+	));
+	assert!(res matches Err(_) || x matches None);
+
+	let msg = failure(|| assert!(res matches Err(Error::Parse)));
+	assert!(msg.ends_with("Assertion failed: res matches Err(Error::Parse)\n- res = Err(\n    Io(\n        2,\n    ),\n)"));
+
+	let msg = failure(|| assert!(x matches Some(n) if *n > 4));
+	assert!(msg.ends_with("Assertion failed: x matches Some(n) if * n > 4\n- x = Some(\n    4,\n)"));
+}
+
+#[test]
+pub fn test_let() {
+	use std::collections::BTreeMap;
+
+	let map = BTreeMap::from([("a", 1), ("b", 2)]);
+
+	assert!(let Some(v) = map.get("a"));
+	assert!(let (Some(&a), Some(&b)) = (map.get("a"), map.get("b")), "missing keys");
+	assert!(all(*v == 1, a + b == 3));
+
+	let msg = failure(|| {
+		assert!(let Some(_) = map.get("c"), "no value for {}", "c");
+	});
+	assert!(msg.ends_with("no value for c\nAssertion failed: let Some(_) = map.get(\"c\")\n- map.get(\"c\") = None"));
+
+	// the code following a failed `let` cannot run, even within a soft scope
+	#[cfg(feature = "std")]
+	{
+		let msg = failure(|| {
+			equator::soft(|| {
+				assert!(let 1..=2 = map.len() + 1);
+			})
+		});
+		assert!(msg.ends_with("Assertion failed: let 1 ..= 2 = map.len() + 1\n- map.len() + 1 = 3"));
+	}
+}
+
+#[test]
+pub fn test_chain() {
+	let lo = 1;
+	let hi = 4;
+	let x = 4;
+
+	assert!(all(lo <= x && x <= hi, lo <= x <= hi, hi > x - 1 >= lo, 0 < lo < x));
+	assert!(not(lo < x < hi));
+
+	let msg = failure(|| assert!(lo <= x < hi));
+	assert!(msg.ends_with("Assertion failed: lo <= x < hi\n- lo = 1\n- x = 4\n- hi = 4\n- violated bound: x < hi"));
+
+	let msg = failure(|| assert!(hi > x + 1 >= lo));
+	assert!(msg.contains("Assertion failed: hi > x + 1 >= lo\n- hi = 4\n- x + 1 = 5\n- lo = 1\n- violated bound: hi > x + 1"));
+
+	// the middle operand is only evaluated once
+	let mut calls = 0;
+	let mut next = || {
+		calls += 1;
+		calls
+	};
+	assert!(lo <= next() < hi);
+	assert!(calls == 1);
+
+	let s = "b";
+	assert!("a" < s <= "b");
+}
+
+#[test]
+pub fn test_in() {
+	let len = 4;
+	let idx = 4;
+	let allowed = ["a", "b"];
+	let item = "c";
+
+	assert!(all(
+		idx - 1 in 0..len,
+		idx in 0..=len,
+		'x' in 'a'..='z',
+		idx in 1..,
+		idx in ..=len,
+		"a" in allowed,
+		"b" in &allowed[1..],
+		not(item in allowed),
+	));
+
+	let msg = failure(|| assert!(idx in 0..len));
+	assert!(msg.contains("Assertion failed: idx in 0 .. len\n- idx = 4\n- 0 .. len = 0..4\n- not below the exclusive upper bound 4"));
+
+	let msg = failure(|| assert!(idx in 5..=8));
+	assert!(msg.ends_with("Assertion failed: idx in 5 ..= 8\n- idx = 4\n- 5 ..= 8 = 5..=8\n- below the lower bound 5"));
+
+	let msg = failure(|| assert!(item in allowed));
+	assert!(msg.ends_with("Assertion failed: item in allowed\n- item = \"c\"\n- allowed = [\n    \"a\",\n    \"b\",\n]"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_in_collections() {
+	use std::collections::BTreeSet;
+
+	let v = (0..20).collect::<Vec<i32>>();
+	let set = BTreeSet::from([1, 2]);
+	assert!(all(3 in v, 1 in set, not(3 in &set)));
+
+	#[cfg(feature = "std")]
+	{
+		let hash = std::collections::HashSet::from([1, 2]);
+		assert!(all(2 in &hash, not(3 in hash)));
+	}
+
+	let msg = failure(|| assert!(-1 in v));
+	assert!(msg.ends_with(
+		"- v = [\n    0,\n    1,\n    2,\n    3,\n    4,\n    5,\n    6,\n    7,\n    8,\n    9,\n    ... and 10 more,\n]\n- not found among 20 elements"
+	));
+}
+
+#[test]
+pub fn test_unwrap() {
+	fn parse(s: &str) -> Result<i32, std::num::ParseIntError> {
+		s.parse()
+	}
+
+	let v = assert!(ok(parse("1")));
+	let e = assert!(err(parse("a")), "{} should not parse", "a");
+	let first = assert!(some([1, 2].first()));
+	assert!(none([0u8; 0].first()));
+	assert!(all(
+		v == 1,
+		*first == 1,
+		ok(parse("2")),
+		not(some(None::<i32>)),
+		none(e.to_string().find('?'))
+	));
+	equator::debug_assert!(ok(parse("3")));
+
+	// functions with the same name as a leaf are called within a block
+	fn some(x: i32) -> bool {
+		x > 0
+	}
+	assert!(all({ some(1) }, not({ some(0) })));
+
+	let msg = failure(|| {
+		assert!(ok(parse("b")));
+	});
+	assert!(msg.ends_with("Assertion failed: ok(parse(\"b\"))\n- parse(\"b\") = Err(\n    ParseIntError {\n        kind: InvalidDigit,\n    },\n)"));
+
+	// only the payload needs to implement `Debug`
+	struct Opaque;
+	let res: Result<Opaque, i32> = Err(2);
+	let opt: Option<Opaque> = None;
+	let msg = failure(|| assert!(all(ok(res), some(opt), err(res))));
+	assert!(msg.ends_with("Assertion failed: ok(res)\n- res = Err(\n    2,\n)\nAssertion failed: some(opt)\n- opt = None"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_quantifiers() {
+	let v = [1, 2, 3, 4, 5, 6, 7, 8];
+	let empty: [i32; 0] = [];
+
+	assert!(all(
+		for_all(x in v.iter() => *x > 0),
+		for_all(x in empty => x > 0),
+		exists(x in v => x % 3 == 0 && x > 3),
+		for_all(i in 0..v.len() => all(v[i] == i as i32 + 1, exists(d in 1..=v[i] => d * d >= v[i]))),
+		not(exists(x in empty => x == 0)),
+	));
+
+	let msg = failure(|| assert!(for_all(x in v => x < 3)));
+	assert!(msg.contains(
+		"Assertion failed: for_all(x in v => x < 3)\n- the condition failed for 6 of 8 elements\n- element 2: x = 3\n  Assertion failed: x < 3\n  - x = 3\n  - 3 = 3\n"
+	));
+	assert!(msg.contains("- element 6: x = 7\n  Assertion failed: x < 3\n  - x = 7\n  - 3 = 3\n- ... and 1 more"));
+
+	let msg = failure(|| assert!(exists(x in [1, 3] => x % 2 == 0)));
+	assert!(msg.contains(
+		"- the condition failed for all 2 elements\n- element 0: x = 1\n  Assertion failed: x % 2 == 0\n  - x % 2 = 1\n  - 0 = 0\n- element 1: x = 3\n  Assertion failed: x % 2 == 0\n  - x % 2 = 1\n  - 0 = 0"
+	));
+
+	let msg = failure(|| assert!(not(exists(x in v => x == 2))));
+	assert!(msg.contains("Unexpectedly held: exists(x in v => x == 2)\n- the condition held for element 1"));
+
+	// only the reported elements are explained, once for the explanation and once for the report
+	use equator::quant::MAX_ELEMENTS;
+	let formatted = std::cell::Cell::new(0);
+	let count = |x: i32| {
+		formatted.set(formatted.get() + 1);
+		x
+	};
+	let msg = failure(std::panic::AssertUnwindSafe(|| assert!(for_all(x in 0..1000 => (x < 0, "{}", count(x))))));
+	assert!(msg.ends_with("- ... and 995 more"));
+	assert!(formatted.get() == 2 * MAX_ELEMENTS);
+}
+
+#[test]
+pub fn test_labels() {
+	let rows = 2;
+	let cols = 3;
+
+	assert!(all("rows" => rows == 2, "shape" => all(rows < cols, cols > 0), r"cols" => cols == 3));
+	assert!(any("square" => rows == cols, cols == 3));
+
+	let msg = failure(|| assert!(all("lhs shape" => rows == cols, rows < cols, "non-empty" => any(rows == 0, cols == 0))));
+	assert!(msg.contains("[lhs shape]\nAssertion failed: rows == cols\n- rows = 2\n- cols = 3\n[non-empty]\nAssertion failed: rows == 0\n"));
+	assert!(!msg.contains("rows < cols"));
+
+	let msg = failure(|| assert!(not(any(rows == 0, "wide" => rows < cols))));
+	assert!(msg.contains("[wide]\nUnexpectedly held: rows < cols\n- rows = 2\n- cols = 3"));
+}
+
+#[test]
+pub fn test_messages() {
+	use std::cell::Cell;
+
+	let rows = 2;
+	let cols = 3;
+	let formatted = Cell::new(0);
+	let count = |x: i32| {
+		formatted.set(formatted.get() + 1);
+		x
+	};
+
+	assert!(all((rows < cols, "too wide: {}", count(cols)), "rows" => (rows == 2, "rows = {rows}")));
+	assert!(formatted.get() == 0);
+
+	let msg =
+		failure(|| assert!(all((rows == cols, "not square: {rows}x{}", cols), (rows > 0, "empty"), "wide" => (rows > cols, "too narrow")), "shapes"));
+	assert!(msg.contains(
+		"shapes\nnot square: 2x3\nAssertion failed: rows == cols\n- rows = 2\n- cols = 3\n[wide]\ntoo narrow\nAssertion failed: rows > cols\n"
+	));
+	assert!(!msg.contains("empty"));
+
+	#[cfg(feature = "alloc")]
+	{
+		let msg = failure(|| assert!(for_all(x in [1, 2, 3] => (x != 2, "{x} is even"))));
+		assert!(msg.contains("- element 1: x = 2\n  2 is even\n  Assertion failed: x != 2\n"));
+	}
+}
@@ -0,0 +1,35 @@
+use common::panic_message;
+use equator::{assert, level};
+
+mod common;
+
+#[test]
+pub fn test_levels() {
+	let x = 1;
+
+	assert!(level = debug; x == 1);
+	assert!(level = expensive; all(x == 1, x < 2), "x = {x}");
+	assert!(level = paranoid; x == 1);
+	assert!(implies(level::paranoid::ENABLED, level::expensive::ENABLED));
+
+	let msg = panic_message(|| assert!(level = debug; x == 2));
+	assert!(msg.is_some() == cfg!(debug_assertions));
+
+	let msg = panic_message(|| assert!(level = expensive; x == 2, "expensive"));
+	assert!(msg.is_some() == level::expensive::ENABLED);
+	if let Some(msg) = msg {
+		assert!(msg.contains("expensive\nAssertion failed: x == 2\n- x = 1\n- 2 = 2"));
+	}
+
+	let msg = panic_message(|| assert!(level = paranoid; x == 2));
+	assert!(msg.is_some() == level::paranoid::ENABLED);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_levels_check() {
+	let x = 1;
+
+	assert!(equator::check!(level = expensive; x == 2).is_err() == level::expensive::ENABLED);
+	assert!(equator::check!(level = paranoid; x == 1).is_ok());
+}
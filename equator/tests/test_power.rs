@@ -0,0 +1,107 @@
+#![cfg(feature = "power")]
+
+use common::failure;
+use equator::{assert, check};
+
+mod common;
+
+#[test]
+pub fn test_power_places() {
+	let x = 3;
+	let y = 3;
+	let msg = failure(|| assert!(x + 1 < y));
+	assert!(msg.ends_with("Intermediate values:\n- x = 3\nAssertion failed: x + 1 < y\n- x + 1 = 4\n- y = 3"));
+}
+
+#[test]
+pub fn test_power_calls() {
+	let a = [1, 2, 3, 4];
+	let stride = 2;
+	let off = 1;
+	let buf = vec![0u8; 8];
+	let msg = failure(|| assert!(a.len() * stride + off == buf.len()));
+	assert!(msg.contains("Intermediate values:\n- a = [\n    1,\n    2,\n    3,\n    4,\n]\n- stride = 2\n- off = 1\n- buf = [\n    0,\n    0,\n    0,\n    0,\n    0,\n    0,\n    0,\n    0,\n]\n- a.len() = 4\nAssertion failed: a.len() * stride + off == buf.len()"));
+
+	struct Point {
+		x: i32,
+		y: i32,
+	}
+	let p = Point { x: 1, y: 2 };
+	let v = vec![1, 2, 3];
+	let msg = failure(|| assert!(v.iter().copied().sum::<i32>() == p.x + p.y));
+	// the formatting of standard iterators is unspecified
+	assert!(msg.contains("Intermediate values:\n- v = [\n    1,\n    2,\n    3,\n]\n- p.x = 1\n- p.y = 2\n- v.iter() = "));
+	assert!(msg.contains("\n- v.iter().copied() = "));
+}
+
+#[test]
+pub fn test_power_evaluated_once() {
+	let mut calls = 0;
+	let mut next = || {
+		calls += 1;
+		calls
+	};
+	let msg = failure(std::panic::AssertUnwindSafe(|| assert!(next() + 1 == 0)));
+	assert!(msg.contains("Intermediate values:\n- next() = 1\nAssertion failed: next() + 1 == 0"));
+	assert!(calls == 1);
+}
+
+#[test]
+pub fn test_power_moves() {
+	struct Opaque;
+
+	// values are formatted as they are produced, before they are moved by the rest of the condition
+	let v = vec![Opaque, Opaque];
+	let w = vec![1];
+	let msg = failure(move || assert!(all(v.into_iter().count() == 1, w.clone().into_iter().sum::<i32>() == 0)));
+	assert!(msg.contains("Intermediate values:\n- w = [\n    1,\n]\n- w.clone() = [\n    1,\n]\n- w.clone().into_iter() = "));
+}
+
+#[test]
+pub fn test_power_check() {
+	fn first_even(v: &[i32]) -> Option<i32> {
+		v.iter().copied().find(|x| x % 2 == 0)
+	}
+	fn checked(v: &[i32]) -> Option<bool> {
+		Some(check!(first_even(v)? + 1 == v.len() as i32).is_ok())
+	}
+
+	let x = 2;
+	assert!(check!(x + 1 == 3).is_ok());
+	assert!(checked(&[1, 3]).is_none());
+	assert!(checked(&[1, 2]) == Some(false));
+
+	let err = check!(x * x == 5).unwrap_err();
+	assert!(err
+		.to_string()
+		.ends_with("Intermediate values:\n- x = 2\nAssertion failed: x * x == 5\n- x * x = 4\n- 5 = 5"));
+}
+
+#[test]
+pub fn test_power_lazy() {
+	use std::cell::Cell;
+
+	#[derive(Copy, Clone)]
+	struct Counted<'a>(&'a Cell<u32>);
+	impl std::fmt::Debug for Counted<'_> {
+		fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+			self.0.set(self.0.get() + 1);
+			f.write_str("Counted")
+		}
+	}
+	impl Counted<'_> {
+		fn count(self) -> u32 {
+			self.0.get()
+		}
+	}
+
+	// copies are only formatted if the condition fails
+	let formatted = Cell::new(0);
+	let c = Counted(&formatted);
+	assert!(c.count() + 1 == 1);
+	assert!(formatted.get() == 0);
+
+	let msg = failure(std::panic::AssertUnwindSafe(|| assert!(c.count() + 1 == 0)));
+	assert!(msg.contains("Intermediate values:\n- c = Counted\n"));
+	assert!(formatted.get() == 1);
+}
@@ -0,0 +1,73 @@
+use common::failure;
+use equator::assert;
+
+mod common;
+
+#[test]
+pub fn test_slice_eq() {
+	let x = [1, 2, 3];
+	let v = vec![1, 2, 3];
+	let s: &[i32] = &v;
+
+	assert!(all(x == [1, 2, 3], v == x, *v == x, s == x, *s == v, x == *s, Some(1) == Some(1), 1 == 1));
+	assert!(not(any(x == [1, 2, 4], v == [1, 2], *s == [0; 0])));
+}
+
+// without `alloc`, a `Vec` is not compared as a sequence
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_slice_mismatch() {
+	let x = Vec::from_iter(0..1000);
+	let mut y = x.clone();
+	y[3] = 0;
+	y.push(1000);
+
+	let msg = failure(|| assert!(x == y));
+	assert!(msg.contains(
+		"Assertion failed: x == y
+- x.len() = 1000
+- y.len() = 1001
+- first mismatch at index 3
+  - [3]: 3 != 0"
+	));
+}
+
+#[test]
+pub fn test_slice_mismatch_truncated() {
+	let x = [0u8; 20];
+	let y = [1u8; 20];
+
+	let msg = failure(|| assert!(x == y));
+	assert!(msg.contains(
+		"- x.len() = 20
+- y.len() = 20
+- first mismatch at index 0
+  - [0]: 0 != 1
+  - [1]: 0 != 1
+  - [2]: 0 != 1
+  - [3]: 0 != 1
+  - [4]: 0 != 1
+  - [5]: 0 != 1
+  - [6]: 0 != 1
+  - [7]: 0 != 1
+  - ... and 12 more"
+	));
+}
+
+#[test]
+pub fn test_slice_held() {
+	let x = [0u8; 20];
+
+	let msg = failure(|| assert!(not(x == x)));
+	assert!(msg.ends_with("Unexpectedly held: x == x\n- x.len() = 20\n- x.len() = 20"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_slice_report() {
+	let x = [1, 2];
+	let report = equator::check!(x == [1, 3]).unwrap_err().report().clone();
+
+	assert!(all(report.op == Some("=="), !report.passed));
+	assert!(report.rhs.unwrap().value == "[\n    1,\n    3,\n]");
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "std")]
+
+use common::failure;
+use equator::assert;
+
+mod common;
+
+#[test]
+pub fn test_soft_ok() {
+	let v = equator::soft(|| {
+		for i in 0..4 {
+			assert!(i < 4);
+		}
+		3
+	});
+	assert!(v == 3);
+}
+
+#[test]
+pub fn test_soft_collects_all() {
+	let msg = failure(|| {
+		equator::soft(|| {
+			for i in 0..4 {
+				assert!(i % 2 == 0, "odd index {i}");
+			}
+			assert!(all(1 == 1, 2 < 1));
+		})
+	});
+
+	assert!(msg.starts_with("3 soft assertion(s) failed\n\n"));
+	let odd_1 = msg.find("odd index 1").unwrap();
+	let odd_3 = msg.find("odd index 3").unwrap();
+	let lt = msg.find("Assertion failed: 2 < 1\n- 2 = 2\n- 1 = 1").unwrap();
+	assert!(all(odd_1 < odd_3, odd_3 < lt));
+	assert!(!msg.contains("1 == 1"));
+}
+
+#[test]
+pub fn test_soft_guard() {
+	let result = std::panic::catch_unwind(|| {
+		let guard = equator::SoftGuard::new();
+		assert!(1 + 1 == 3);
+		assert!(guard.failure_count() == 1);
+	});
+	assert!(result.is_err());
+
+	// the guard no longer intercepts failures once dropped
+	let msg = failure(|| assert!(1 + 1 == 3));
+	assert!(msg.starts_with("Assertion failed at"));
+}
+
+#[test]
+pub fn test_soft_nested() {
+	let msg = failure(|| {
+		equator::soft(|| {
+			assert!(1 == 2, "outer");
+			equator::soft(|| assert!(2 == 3, "inner"));
+			assert!(3 == 4, "after");
+		})
+	});
+
+	assert!(msg.starts_with("3 soft assertion(s) failed\n\n"));
+	let outer = msg.find("outer").unwrap();
+	let inner = msg.find("inner").unwrap();
+	let after = msg.find("after").unwrap();
+	assert!(all(outer < inner, inner < after));
+}
+
+#[test]
+pub fn test_soft_panicking() {
+	// the failures are printed to stderr rather than lost, and the original panic goes through
+	let msg = failure(|| {
+		equator::soft(|| {
+			assert!(1 == 2);
+			equator::soft(|| {
+				assert!(2 == 3);
+				panic!("unrelated");
+			});
+		})
+	});
+	assert!(msg == "unrelated");
+
+	// the guards were restored, so failures panic right away again
+	let msg = failure(|| assert!(1 == 2));
+	assert!(msg.starts_with("Assertion failed at"));
+}
@@ -0,0 +1,5 @@
+#[test]
+pub fn test_ui() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,7 @@
+use equator::assert;
+
+fn main() {
+	let a = 1.0;
+	let b = 2.0;
+	assert!(a ~ b);
+}
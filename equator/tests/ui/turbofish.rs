@@ -0,0 +1,7 @@
+use equator::assert;
+
+fn main() {
+	let a = 1;
+	let b = 2;
+	assert!(a == b.into::<i32());
+}